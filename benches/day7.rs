@@ -0,0 +1,25 @@
+#![feature(test)]
+extern crate test;
+
+use aoc_2022::day7::{filesystem::FileSystem, parse_input, synthetic_log};
+use test::Bencher;
+
+// 349_525 directories, four per level.
+#[bench]
+fn wide_tree_sizes(b: &mut Bencher) {
+    let fs = FileSystem::from_log(&parse_input(synthetic_log(4, 9, 1))).unwrap();
+    b.iter(|| fs.dir_sizes());
+}
+
+// 200_001 directories nested inside each other.
+#[bench]
+fn deep_chain_sizes(b: &mut Bencher) {
+    let fs = FileSystem::from_log(&parse_input(synthetic_log(1, 200_000, 1))).unwrap();
+    b.iter(|| fs.dir_sizes());
+}
+
+#[bench]
+fn wide_tree_from_log(b: &mut Bencher) {
+    let log = parse_input(synthetic_log(4, 7, 2));
+    b.iter(|| FileSystem::from_log(&log).unwrap());
+}
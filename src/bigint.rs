@@ -0,0 +1,139 @@
+use std::cmp::Ordering;
+use std::ops::{Add, Mul};
+
+// An unsigned integer of any size, with just the operations the puzzles need. Little endian 64
+// bit limbs, never with a trailing zero limb, so zero has none.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u64>,
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        BigUint::normalized(vec![value])
+    }
+}
+
+impl BigUint {
+    fn normalized(mut limbs: Vec<u64>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigUint { limbs }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            Some(last) => 64 * self.limbs.len() as u64 - last.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    // Long division by a single limb.
+    pub fn div_rem(&self, divisor: u64) -> (BigUint, u64) {
+        assert!(divisor != 0, "Division by zero");
+        let mut quotient = vec![0; self.limbs.len()];
+        let mut remainder = 0u128;
+        for (idx, &limb) in self.limbs.iter().enumerate().rev() {
+            let current = (remainder << 64) | limb as u128;
+            quotient[idx] = (current / divisor as u128) as u64;
+            remainder = current % divisor as u128;
+        }
+        (BigUint::normalized(quotient), remainder as u64)
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry = 0u128;
+        for idx in 0..self.limbs.len().max(other.limbs.len()) {
+            let sum = *self.limbs.get(idx).unwrap_or(&0) as u128 + *other.limbs.get(idx).unwrap_or(&0) as u128 + carry;
+            limbs.push(sum as u64);
+            carry = sum >> 64;
+        }
+        limbs.push(carry as u64);
+        BigUint::normalized(limbs)
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs = vec![0u64; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let product = a as u128 * b as u128 + limbs[i + j] as u128 + carry;
+                limbs[i + j] = product as u64;
+                carry = product >> 64;
+            }
+            limbs[i + other.limbs.len()] = carry as u64;
+        }
+        BigUint::normalized(limbs)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::fmt::Display for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // Peels off 19 decimal digits at a time, the most a limb can hold.
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut chunks = vec![];
+        let mut rest = self.clone();
+        loop {
+            let (quotient, chunk) = rest.div_rem(CHUNK);
+            chunks.push(chunk);
+            if quotient.is_zero() {
+                break;
+            }
+            rest = quotient;
+        }
+        let mut chunks = chunks.iter().rev();
+        write!(f, "{}", chunks.next().unwrap())?;
+        chunks.try_for_each(|chunk| write!(f, "{chunk:019}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let max = BigUint::from(u64::MAX);
+        let one = BigUint::from(1);
+        let two_64 = &max + &one;
+        assert_eq!("18446744073709551616", two_64.to_string());
+        assert_eq!(65, two_64.bits());
+        let two_128 = &two_64 * &two_64;
+        assert_eq!("340282366920938463463374607431768211456", two_128.to_string());
+        assert_eq!((&two_64 * &BigUint::from(1 << 32), 0), two_128.div_rem(1 << 32));
+        assert_eq!(4, two_128.div_rem(7).1);
+        assert!(two_64 > max && max > one && BigUint::default() < one);
+        assert_eq!("0", BigUint::from(0).to_string());
+        assert!(BigUint::from(0).is_zero());
+        assert_eq!(BigUint::from(0), &BigUint::from(0) * &max);
+    }
+}
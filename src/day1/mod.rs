@@ -1,4 +1,10 @@
-use aoc_downloader::download_day;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+
+use crate::utils::download_day;
+
+pub mod report;
 
 const DAY: u32 = 1;
 
@@ -11,18 +17,118 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-fn parse_input(input: Vec<String>) -> Vec<u32> {
-    let mut findings = vec![];
-    let mut elf = 0;
-    for line in input {
-        if !line.is_empty() {
-            elf += line.parse::<u32>().unwrap();
-        } else {
-            findings.push(elf);
-            elf = 0;
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Elf {
+    pub index: usize,
+    pub calories: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Inventory {
+    pub index: usize,
+    pub items: Vec<u32>,
+}
+
+impl Inventory {
+    // Single items fit in a u32, a whole elf's total might not.
+    pub fn calories(&self) -> u64 {
+        self.items.iter().map(|&item| item as u64).sum()
+    }
+}
+
+// Reads one elf per call to `next`, so the inventory is never held in memory as a whole. Any
+// number of blank lines separate two elves, a missing blank line at the end is fine as well.
+pub struct Inventories<I> {
+    lines: I,
+    index: usize,
+}
+
+impl<I> Inventories<I> {
+    pub fn new(lines: I) -> Self {
+        Inventories { lines, index: 0 }
+    }
+}
+
+impl<I, S> Iterator for Inventories<I>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    type Item = Inventory;
+
+    fn next(&mut self) -> Option<Inventory> {
+        let mut items = vec![];
+        for line in self.lines.by_ref() {
+            let line = line.as_ref().trim();
+            if line.is_empty() {
+                if !items.is_empty() {
+                    break;
+                }
+                continue;
+            }
+            items.push(line.parse::<u32>().unwrap());
+        }
+        if items.is_empty() {
+            return None;
+        }
+        let inventory = Inventory {
+            index: self.index,
+            items,
+        };
+        self.index += 1;
+        Some(inventory)
+    }
+}
+
+pub struct Elves<I>(Inventories<I>);
+
+impl<I> Elves<I> {
+    pub fn new(lines: I) -> Self {
+        Elves(Inventories::new(lines))
+    }
+}
+
+impl<I, S> Iterator for Elves<I>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    type Item = Elf;
+
+    fn next(&mut self) -> Option<Elf> {
+        self.0.next().map(|inventory| Elf {
+            index: inventory.index,
+            calories: inventory.calories(),
+        })
+    }
+}
+
+pub fn inventories_from_reader<R: BufRead>(reader: R) -> Inventories<impl Iterator<Item = String>> {
+    Inventories::new(reader.lines().map(|line| line.unwrap()))
+}
+
+pub fn elves_from_reader<R: BufRead>(reader: R) -> Elves<impl Iterator<Item = String>> {
+    Elves::new(reader.lines().map(|line| line.unwrap()))
+}
+
+// The `k` elves carrying the most calories, best first. Only `k` elves are kept around at any
+// time; on equal totals the elf that came first wins.
+pub fn top_k(elves: impl Iterator<Item = Elf>, k: usize) -> Vec<Elf> {
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for elf in elves {
+        heap.push(Reverse((elf.calories, Reverse(elf.index))));
+        if heap.len() > k {
+            heap.pop();
         }
     }
-    findings
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((calories, Reverse(index)))| Elf { index, calories })
+        .collect()
+}
+
+pub fn parse_input(input: Vec<String>) -> Vec<u64> {
+    Elves::new(input.iter()).map(|elf| elf.calories).collect()
 }
 
 pub fn run_day() {
@@ -36,14 +142,16 @@ pub fn run_day() {
     );
 }
 
-fn part1(input: &[u32]) -> u32 {
+pub fn part1(input: &[u64]) -> u64 {
     *input.iter().max().unwrap()
 }
 
-fn part2(input: &[u32]) -> u32 {
-    let mut input = input.to_owned();
-    input.sort_by(|a, b| a.cmp(b).reverse());
-    input.iter().take(3).sum()
+pub fn part2(input: &[u64]) -> u64 {
+    let elves = input
+        .iter()
+        .enumerate()
+        .map(|(index, &calories)| Elf { index, calories });
+    top_k(elves, 3).iter().map(|elf| elf.calories).sum()
 }
 
 #[cfg(test)]
@@ -61,4 +169,27 @@ mod tests {
         let input = parse_input(get_input());
         assert_eq!(208191, part2(&input));
     }
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+
+    #[test]
+    fn day1_last_elf_without_blank_line() {
+        let input = parse_input(EXAMPLE.lines().map(String::from).collect());
+        assert_eq!(vec![6000, 4000, 11000, 24000, 10000], input);
+        assert_eq!(45000, part2(&input));
+    }
+
+    #[test]
+    fn day1_top_k_from_reader() {
+        let top = top_k(elves_from_reader(std::io::Cursor::new(EXAMPLE)), 2);
+        assert_eq!(vec![Elf { index: 3, calories: 24000 }, Elf { index: 2, calories: 11000 }], top);
+    }
+
+    #[test]
+    fn day1_totals_beyond_u32() {
+        let input = parse_input(["4000000000", "4000000000", "", "1"].map(String::from).to_vec());
+        assert_eq!(vec![8_000_000_000, 1], input);
+        assert_eq!(8_000_000_000, part1(&input));
+        assert_eq!(8_000_000_001, part2(&input));
+    }
 }
@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use super::Inventory;
+
+const PERCENTILES: [u32; 6] = [10, 25, 50, 75, 90, 99];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub median: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bucket {
+    pub start: u64,
+    pub end: u64,
+    pub count: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct InventoryReport {
+    pub elves: usize,
+    pub items: usize,
+    pub items_per_elf: Summary,
+    pub calories: Summary,
+    pub percentiles: Vec<(u32, u64)>,
+    pub histogram: Vec<Bucket>,
+    pub identical_totals: Vec<(u64, Vec<usize>)>,
+}
+
+fn summarise(sorted: &[u64]) -> Summary {
+    let n = sorted.len();
+    let median = if n % 2 == 1 {
+        sorted[n / 2] as f64
+    } else {
+        (sorted[n / 2 - 1] as f64 + sorted[n / 2] as f64) / 2.0
+    };
+    Summary {
+        min: sorted[0],
+        max: sorted[n - 1],
+        mean: sorted.iter().map(|&v| v as f64).sum::<f64>() / n as f64,
+        median,
+    }
+}
+
+// Nearest-rank percentile, always one of the observed totals.
+fn percentile(sorted: &[u64], p: u32) -> u64 {
+    let rank = (p as usize * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+fn histogram(sorted: &[u64], buckets: usize) -> Vec<Bucket> {
+    let min = sorted[0];
+    let max = sorted[sorted.len() - 1];
+    let width = (max - min) / buckets as u64 + 1;
+    let mut histogram = (0..buckets as u64)
+        .map(|i| Bucket {
+            start: min + i * width,
+            end: min + (i + 1) * width - 1,
+            count: 0,
+        })
+        .collect::<Vec<_>>();
+    for &total in sorted {
+        histogram[((total - min) / width) as usize].count += 1;
+    }
+    histogram
+}
+
+impl InventoryReport {
+    // Consumes the inventories one by one, only the per-elf totals and item counts are kept.
+    pub fn new(inventories: impl Iterator<Item = Inventory>, buckets: usize) -> Option<Self> {
+        let mut totals = vec![];
+        let mut item_counts = vec![];
+        let mut by_total: BTreeMap<u64, Vec<usize>> = BTreeMap::new();
+        for inventory in inventories {
+            let total = inventory.calories();
+            totals.push(total);
+            item_counts.push(inventory.items.len() as u64);
+            by_total.entry(total).or_default().push(inventory.index);
+        }
+        if totals.is_empty() {
+            return None;
+        }
+
+        totals.sort_unstable();
+        item_counts.sort_unstable();
+        Some(InventoryReport {
+            elves: totals.len(),
+            items: item_counts.iter().map(|&c| c as usize).sum(),
+            items_per_elf: summarise(&item_counts),
+            calories: summarise(&totals),
+            percentiles: PERCENTILES.iter().map(|&p| (p, percentile(&totals, p))).collect(),
+            histogram: histogram(&totals, buckets.max(1)),
+            identical_totals: by_total
+                .into_iter()
+                .rev()
+                .filter(|(_, elves)| elves.len() > 1)
+                .collect(),
+        })
+    }
+
+    pub fn to_table(&self) -> String {
+        let mut table = String::new();
+        let summary = |s: &Summary| {
+            format!("min {}, max {}, mean {:.2}, median {}", s.min, s.max, s.mean, s.median)
+        };
+        writeln!(table, "Elves            {}", self.elves).unwrap();
+        writeln!(table, "Items            {}", self.items).unwrap();
+        writeln!(table, "Items per elf    {}", summary(&self.items_per_elf)).unwrap();
+        writeln!(table, "Calories         {}", summary(&self.calories)).unwrap();
+        let percentiles = self
+            .percentiles
+            .iter()
+            .map(|(p, v)| format!("p{p} {v}"))
+            .collect::<Vec<_>>();
+        writeln!(table, "Percentiles      {}", percentiles.join(", ")).unwrap();
+
+        writeln!(table, "Histogram").unwrap();
+        let widest = self.histogram.iter().map(|b| b.count).max().unwrap_or(0).max(1);
+        for bucket in &self.histogram {
+            let bar = "#".repeat((bucket.count * 40).div_ceil(widest));
+            writeln!(table, "\t{:>8} - {:>8}  {:<40} {}", bucket.start, bucket.end, bar, bucket.count).unwrap();
+        }
+
+        writeln!(table, "Identical totals").unwrap();
+        for (total, elves) in &self.identical_totals {
+            let elves = elves.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+            writeln!(table, "\t{:>8}  elves {}", total, elves.join(", ")).unwrap();
+        }
+        table
+    }
+
+    pub fn to_json(&self) -> String {
+        let summary = |s: &Summary| {
+            format!(
+                "{{\"min\":{},\"max\":{},\"mean\":{},\"median\":{}}}",
+                s.min, s.max, s.mean, s.median
+            )
+        };
+        let percentiles = self
+            .percentiles
+            .iter()
+            .map(|(p, v)| format!("\"p{p}\":{v}"))
+            .collect::<Vec<_>>();
+        let histogram = self
+            .histogram
+            .iter()
+            .map(|b| format!("{{\"start\":{},\"end\":{},\"count\":{}}}", b.start, b.end, b.count))
+            .collect::<Vec<_>>();
+        let identical = self
+            .identical_totals
+            .iter()
+            .map(|(total, elves)| format!("{{\"calories\":{},\"elves\":{:?}}}", total, elves))
+            .collect::<Vec<_>>();
+        format!(
+            "{{\"elves\":{},\"items\":{},\"items_per_elf\":{},\"calories\":{},\"percentiles\":{{{}}},\"histogram\":[{}],\"identical_totals\":[{}]}}",
+            self.elves,
+            self.items,
+            summary(&self.items_per_elf),
+            summary(&self.calories),
+            percentiles.join(","),
+            histogram.join(","),
+            identical.join(","),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Inventories;
+    use super::*;
+
+    #[test]
+    fn day1_report() {
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n\n4000";
+        let report = InventoryReport::new(Inventories::new(input.lines()), 4).unwrap();
+        assert_eq!(6, report.elves);
+        assert_eq!(11, report.items);
+        assert_eq!(Summary { min: 1, max: 3, mean: 11.0 / 6.0, median: 1.5 }, report.items_per_elf);
+        assert_eq!(4000, report.calories.min);
+        assert_eq!(8000.0, report.calories.median);
+        assert_eq!((50, 6000), report.percentiles[2]);
+        assert_eq!(Bucket { start: 4000, end: 9000, count: 3 }, report.histogram[0]);
+        assert_eq!(vec![(4000, vec![1, 5])], report.identical_totals);
+
+        let input = "4000000000\n4000000000\n\n1";
+        let report = InventoryReport::new(Inventories::new(input.lines()), 2).unwrap();
+        assert_eq!((1, 8_000_000_000), (report.calories.min, report.calories.max));
+        assert_eq!(Bucket { start: 4_000_000_001, end: 8_000_000_000, count: 1 }, report.histogram[1]);
+    }
+}
@@ -0,0 +1,210 @@
+use super::cpu::Instruction;
+use super::{CRT_HEIGHT, CRT_WIDTH};
+
+const PIXELS: usize = CRT_WIDTH * CRT_HEIGHT;
+
+// X only matters relative to the columns, every value left of -2 or right of the screen draws
+// like -2 and so is never needed.
+const MIN_X: i64 = -2;
+const MAX_X: i64 = CRT_WIDTH as i64 + 1;
+const VALUES: usize = (MAX_X - MIN_X + 1) as usize;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AssembleError {
+    Size { rows: usize, columns: usize },
+    Pixel { row: usize, column: usize, found: char },
+    // No program draws the picture up to and including this pixel.
+    Undrawable { row: usize, column: usize },
+}
+
+impl std::fmt::Display for AssembleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Size { rows, columns } => {
+                write!(f, "Picture is {columns}x{rows}, expected {CRT_WIDTH}x{CRT_HEIGHT}")
+            }
+            Self::Pixel { row, column, found } => write!(f, "Unknown pixel {found} at {row}, {column}"),
+            Self::Undrawable { row, column } => write!(f, "Pixel {row}, {column} can't be drawn"),
+        }
+    }
+}
+
+// Reads a picture drawn with `#` and `.`, blank lines are skipped so the output of part 2 can be
+// read back.
+pub fn parse_picture(picture: &str) -> Result<Vec<bool>, AssembleError> {
+    let rows = picture.lines().filter(|line| !line.is_empty()).collect::<Vec<_>>();
+    if rows.len() != CRT_HEIGHT || rows.iter().any(|row| row.chars().count() != CRT_WIDTH) {
+        return Err(AssembleError::Size {
+            rows: rows.len(),
+            columns: rows.iter().map(|row| row.chars().count()).max().unwrap_or(0),
+        });
+    }
+    rows.iter()
+        .enumerate()
+        .flat_map(|(row, line)| line.chars().enumerate().map(move |(column, pixel)| (row, column, pixel)))
+        .map(|(row, column, pixel)| match pixel {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            found => Err(AssembleError::Pixel { row, column, found }),
+        })
+        .collect()
+}
+
+fn draws(picture: &[bool], cycle: usize, x: i64) -> bool {
+    picture[cycle] == ((x - (cycle % CRT_WIDTH) as i64).abs() <= 1)
+}
+
+fn value(idx: usize) -> i64 {
+    idx as i64 + MIN_X
+}
+
+// The shortest program drawing `picture`. The search runs backwards over (cycle, X): a `noop`
+// draws one pixel with the current X, an `addx` two pixels before X can jump to any value.
+pub fn assemble(picture: &[bool]) -> Result<Vec<Instruction>, AssembleError> {
+    if picture.len() != PIXELS {
+        return Err(AssembleError::Size {
+            rows: picture.len().div_ceil(CRT_WIDTH),
+            columns: CRT_WIDTH,
+        });
+    }
+    // The fewest instructions left from every state, the best jump target from every cycle.
+    let mut cost = vec![[None::<usize>; VALUES]; PIXELS + 1];
+    let mut best = vec![None::<(usize, usize)>; PIXELS + 1];
+    cost[PIXELS] = [Some(0); VALUES];
+    best[PIXELS] = Some((0, 0));
+    for cycle in (0..PIXELS).rev() {
+        for idx in 0..VALUES {
+            let x = value(idx);
+            if !draws(picture, cycle, x) {
+                continue;
+            }
+            let noop = cost[cycle + 1][idx];
+            let addx = match best.get(cycle + 2) {
+                Some(Some((cost, _))) if draws(picture, cycle + 1, x) => Some(*cost),
+                _ => None,
+            };
+            cost[cycle][idx] = match (noop, addx) {
+                (Some(noop), Some(addx)) => Some(noop.min(addx) + 1),
+                (noop, addx) => noop.or(addx).map(|cost| cost + 1),
+            };
+        }
+        best[cycle] = (0..VALUES)
+            .filter_map(|idx| cost[cycle][idx].map(|cost| (cost, idx)))
+            .min();
+    }
+
+    let start = (1 - MIN_X) as usize;
+    if cost[0][start].is_none() {
+        let cycle = furthest(picture);
+        return Err(AssembleError::Undrawable {
+            row: cycle / CRT_WIDTH,
+            column: cycle % CRT_WIDTH,
+        });
+    }
+
+    // Noops are preferred on ties, so X only changes when it has to.
+    let mut program = vec![];
+    let (mut cycle, mut idx) = (0, start);
+    while cycle < PIXELS {
+        let remaining = cost[cycle][idx].unwrap();
+        if cost[cycle + 1][idx] == Some(remaining - 1) {
+            program.push(Instruction::noop());
+            cycle += 1;
+        } else {
+            let (_, target) = best[cycle + 2].unwrap();
+            let target = if cycle + 2 == PIXELS { idx } else { target };
+            program.push(Instruction::addx(value(target) - value(idx)));
+            (cycle, idx) = (cycle + 2, target);
+        }
+    }
+    Ok(program)
+}
+
+// The first pixel every program starting from X = 1 gets wrong.
+fn furthest(picture: &[bool]) -> usize {
+    let mut reached = vec![[false; VALUES]; PIXELS + 1];
+    reached[0][(1 - MIN_X) as usize] = true;
+    for cycle in 0..PIXELS {
+        for idx in 0..VALUES {
+            let x = value(idx);
+            if !reached[cycle][idx] || !draws(picture, cycle, x) {
+                continue;
+            }
+            reached[cycle + 1][idx] = true;
+            if cycle + 2 <= PIXELS && draws(picture, cycle + 1, x) {
+                reached[cycle + 2] = [true; VALUES];
+            }
+        }
+    }
+    // Every state on the last cycle reached draws its pixel wrong, or it would have gone further.
+    (0..=PIXELS).rev().find(|&cycle| reached[cycle].contains(&true)).unwrap()
+}
+
+pub fn listing(program: &[Instruction]) -> String {
+    program.iter().map(|instruction| format!("{instruction}\n")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::cpu::{Cpu, InstructionSet};
+    use super::super::Crt;
+    use super::*;
+
+    fn draw(program: &[Instruction]) -> Crt {
+        let mut crt = Crt::default();
+        Cpu::new(InstructionSet::default(), program.to_vec()).run(&mut [&mut crt]);
+        crt
+    }
+
+    #[test]
+    fn day10_assemble_round_trip() {
+        let picture = "
+####.#..#.###..####.###....##..##..#....
+#....#..#.#..#....#.#..#....#.#..#.#....
+###..####.#..#...#..#..#....#.#....#....
+#....#..#.###...#...###.....#.#.##.#....
+#....#..#.#....#....#....#..#.#..#.#....
+####.#..#.#....####.#.....##...###.####.
+";
+        let pixels = parse_picture(picture).unwrap();
+        let program = assemble(&pixels).unwrap();
+        let crt = draw(&program);
+        assert_eq!(pixels, crt.pixels);
+        assert_eq!(&picture[1..], crt.render());
+
+        let parsed = InstructionSet::default();
+        let listing = listing(&program);
+        assert_eq!(program, listing.lines().map(|line| parsed.parse(line).unwrap()).collect::<Vec<_>>());
+
+        // Whatever a program draws can be drawn again, with at most as many instructions.
+        let mut seed = 7u64;
+        let mut program = vec![];
+        while program.iter().map(|op: &Instruction| op.operands.len() + 1).sum::<usize>() < PIXELS {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            program.push(match seed >> 60 {
+                0..=5 => Instruction::noop(),
+                r => Instruction::addx((seed >> 32) as i64 % 7 - 3 + r as i64 % 2),
+            });
+        }
+        let pixels = draw(&program).pixels[..PIXELS].to_vec();
+        let assembled = assemble(&pixels).unwrap();
+        assert!(assembled.len() <= program.len());
+        assert_eq!(pixels, draw(&assembled).pixels);
+    }
+
+    #[test]
+    fn day10_undrawable_pictures() {
+        let mut pixels = vec![true; PIXELS];
+        assert_eq!(pixels, draw(&assemble(&pixels).unwrap()).pixels);
+        pixels = vec![false; PIXELS];
+        assert_eq!(Err(AssembleError::Undrawable { row: 0, column: 0 }), assemble(&pixels));
+        pixels[0] = true;
+        assert_eq!(Err(AssembleError::Undrawable { row: 0, column: 1 }), assemble(&pixels));
+        assert_eq!(Err(AssembleError::Size { rows: 1, columns: 40 }), assemble(&pixels[..40]));
+        assert_eq!(Err(AssembleError::Size { rows: 1, columns: 2 }), parse_picture("#."));
+        assert_eq!(
+            Err(AssembleError::Pixel { row: 0, column: 1, found: 'x' }),
+            parse_picture(&format!("#x{}\n", ".".repeat(38)).repeat(6))
+        );
+    }
+}
@@ -0,0 +1,319 @@
+use std::fmt::Write;
+
+// One entry of the instruction table. `execute` gets the value of X and the operands and returns
+// the new X, it runs at the end of the instruction's last cycle.
+#[derive(Clone, Copy, Debug)]
+pub struct Opcode {
+    pub name: &'static str,
+    pub cycles: usize,
+    pub operands: usize,
+    pub execute: fn(i64, &[i64]) -> i64,
+}
+
+pub const NOOP: Opcode = Opcode {
+    name: "noop",
+    cycles: 1,
+    operands: 0,
+    execute: |x, _| x,
+};
+
+pub const ADDX: Opcode = Opcode {
+    name: "addx",
+    cycles: 2,
+    operands: 1,
+    execute: |x, operands| x + operands[0],
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub name: &'static str,
+    pub operands: Vec<i64>,
+}
+
+impl Instruction {
+    pub fn noop() -> Self {
+        Instruction { name: NOOP.name, operands: vec![] }
+    }
+
+    pub fn addx(value: i64) -> Self {
+        Instruction { name: ADDX.name, operands: vec![value] }
+    }
+}
+
+impl std::fmt::Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        self.operands.iter().try_for_each(|operand| write!(f, " {operand}"))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    UnknownInstruction { name: String },
+    Operands { name: String, expected: usize, found: usize },
+    Operand { text: String },
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::UnknownInstruction { name } => write!(f, "Unknown instruction {name}"),
+            Self::Operands { name, expected, found } => {
+                write!(f, "{name} takes {expected} operands, found {found}")
+            }
+            Self::Operand { text } => write!(f, "Operand {text} is not a number"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct InstructionSet {
+    opcodes: Vec<Opcode>,
+}
+
+impl Default for InstructionSet {
+    fn default() -> Self {
+        InstructionSet { opcodes: vec![NOOP, ADDX] }
+    }
+}
+
+impl InstructionSet {
+    // Adds an opcode or replaces the one with the same name.
+    pub fn add(&mut self, opcode: Opcode) {
+        assert!(opcode.cycles > 0, "{} has to take at least one cycle", opcode.name);
+        match self.opcodes.iter_mut().find(|known| known.name == opcode.name) {
+            Some(known) => *known = opcode,
+            None => self.opcodes.push(opcode),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Opcode> {
+        self.opcodes.iter().find(|opcode| opcode.name == name)
+    }
+
+    pub fn parse(&self, line: &str) -> Result<Instruction, ParseError> {
+        let mut words = line.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let opcode = self
+            .get(name)
+            .ok_or_else(|| ParseError::UnknownInstruction { name: name.to_string() })?;
+        let operands = words
+            .map(|word| word.parse::<i64>().map_err(|_| ParseError::Operand { text: word.to_string() }))
+            .collect::<Result<Vec<_>, _>>()?;
+        if operands.len() != opcode.operands {
+            return Err(ParseError::Operands {
+                name: name.to_string(),
+                expected: opcode.operands,
+                found: operands.len(),
+            });
+        }
+        Ok(Instruction { name: opcode.name, operands })
+    }
+}
+
+// Sees every cycle while it runs, `cycle` counts from one and `x` is the value during the cycle.
+pub trait Observer {
+    fn tick(&mut self, cycle: u64, x: i64);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Condition {
+    Equal,
+    NotEqual,
+    Less,
+    Greater,
+}
+
+// A cycle breakpoint stops before that cycle runs, a register breakpoint right after an
+// instruction sets X so the condition holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Breakpoint {
+    Cycle(u64),
+    X(Condition, i64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stop {
+    Halted,
+    Breakpoint(usize),
+}
+
+// Runs a program one cycle at a time. Puzzle logic lives in observers, the core only knows the
+// instruction table.
+#[derive(Clone, Debug)]
+pub struct Cpu {
+    pub set: InstructionSet,
+    pub program: Vec<Instruction>,
+    pub x: i64,
+    // Cycles completed so far.
+    pub cycle: u64,
+    // The instruction running and how many of its cycles are done.
+    pub pc: usize,
+    pub progress: usize,
+    pub breakpoints: Vec<Breakpoint>,
+    // The cycle count a cycle breakpoint last stopped at, so running again moves on.
+    paused: Option<u64>,
+}
+
+impl Cpu {
+    pub fn new(set: InstructionSet, program: Vec<Instruction>) -> Self {
+        Cpu {
+            set,
+            program,
+            x: 1,
+            cycle: 0,
+            pc: 0,
+            progress: 0,
+            breakpoints: vec![],
+            paused: None,
+        }
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    // Runs a single cycle, returns false once the program is done.
+    pub fn step(&mut self, observers: &mut [&mut dyn Observer]) -> bool {
+        let Some(instruction) = self.program.get(self.pc) else {
+            return false;
+        };
+        let opcode = self
+            .set
+            .get(instruction.name)
+            .unwrap_or_else(|| panic!("Unknown instruction {}", instruction.name));
+        self.cycle += 1;
+        observers.iter_mut().for_each(|observer| observer.tick(self.cycle, self.x));
+        self.progress += 1;
+        if self.progress == opcode.cycles {
+            self.x = (opcode.execute)(self.x, &instruction.operands);
+            self.pc += 1;
+            self.progress = 0;
+        }
+        true
+    }
+
+    fn cycle_breakpoint(&self) -> Option<usize> {
+        if self.is_halted() || self.paused == Some(self.cycle) {
+            return None;
+        }
+        self.breakpoints
+            .iter()
+            .position(|&breakpoint| breakpoint == Breakpoint::Cycle(self.cycle + 1))
+    }
+
+    fn register_breakpoint(&self, previous: i64) -> Option<usize> {
+        if self.x == previous {
+            return None;
+        }
+        self.breakpoints.iter().position(|breakpoint| match *breakpoint {
+            Breakpoint::Cycle(_) => false,
+            Breakpoint::X(Condition::Equal, value) => self.x == value,
+            Breakpoint::X(Condition::NotEqual, value) => self.x != value,
+            Breakpoint::X(Condition::Less, value) => self.x < value,
+            Breakpoint::X(Condition::Greater, value) => self.x > value,
+        })
+    }
+
+    // Runs until the program ends or a breakpoint is hit. Cycle breakpoints are checked before
+    // every cycle, including the first, and calling it again continues past the breakpoint.
+    pub fn run(&mut self, observers: &mut [&mut dyn Observer]) -> Stop {
+        loop {
+            if let Some(idx) = self.cycle_breakpoint() {
+                self.paused = Some(self.cycle);
+                return Stop::Breakpoint(idx);
+            }
+            let previous = self.x;
+            if !self.step(observers) {
+                return Stop::Halted;
+            }
+            if let Some(idx) = self.register_breakpoint(previous) {
+                return Stop::Breakpoint(idx);
+            }
+        }
+    }
+}
+
+// The value of X during every cycle.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trace {
+    pub values: Vec<(u64, i64)>,
+}
+
+impl Observer for Trace {
+    fn tick(&mut self, cycle: u64, x: i64) {
+        self.values.push((cycle, x));
+    }
+}
+
+impl Trace {
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("cycle,x\n");
+        for (cycle, x) in &self.values {
+            writeln!(out, "{cycle},{x}").unwrap();
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program(lines: &[&str]) -> Vec<Instruction> {
+        lines.iter().map(|line| InstructionSet::default().parse(line).unwrap()).collect()
+    }
+
+    #[test]
+    fn day10_cpu_debugger() {
+        let mut cpu = Cpu::new(InstructionSet::default(), program(&["noop", "addx 3", "addx -5"]));
+        let mut trace = Trace::default();
+        cpu.breakpoints = vec![Breakpoint::Cycle(3), Breakpoint::X(Condition::Less, 0)];
+        assert_eq!(Stop::Breakpoint(0), cpu.run(&mut [&mut trace]));
+        assert_eq!((2, 1, 1), (cpu.cycle, cpu.pc, cpu.x));
+        assert!(cpu.step(&mut [&mut trace]));
+        assert_eq!(4, cpu.x);
+        assert_eq!(Stop::Breakpoint(1), cpu.run(&mut [&mut trace]));
+        assert_eq!((5, -1), (cpu.cycle, cpu.x));
+        assert_eq!(Stop::Halted, cpu.run(&mut [&mut trace]));
+        assert!(cpu.is_halted());
+        assert_eq!("cycle,x\n1,1\n2,1\n3,1\n4,4\n5,4\n", trace.to_csv());
+    }
+
+    #[test]
+    fn day10_first_cycle_breakpoint() {
+        let mut cpu = Cpu::new(InstructionSet::default(), program(&["noop", "noop"]));
+        cpu.breakpoints = vec![Breakpoint::Cycle(1), Breakpoint::Cycle(2)];
+        assert_eq!(Stop::Breakpoint(0), cpu.run(&mut []));
+        assert_eq!(0, cpu.cycle);
+        assert_eq!(Stop::Breakpoint(1), cpu.run(&mut []));
+        assert_eq!(1, cpu.cycle);
+        assert_eq!(Stop::Halted, cpu.run(&mut []));
+        assert_eq!(2, cpu.cycle);
+    }
+
+    #[test]
+    fn day10_instruction_set() {
+        let mut set = InstructionSet::default();
+        set.add(Opcode {
+            name: "mulx",
+            cycles: 3,
+            operands: 1,
+            execute: |x, operands| x * operands[0],
+        });
+        let program = vec![set.parse("mulx 7").unwrap(), set.parse("addx -2").unwrap()];
+        assert_eq!("mulx 7", program[0].to_string());
+        let mut cpu = Cpu::new(set.clone(), program);
+        let mut trace = Trace::default();
+        while cpu.step(&mut [&mut trace]) {}
+        assert_eq!((5, 5), (cpu.cycle, cpu.x));
+        assert_eq!(vec![1, 1, 1, 7, 7], trace.values.iter().map(|&(_, x)| x).collect::<Vec<_>>());
+
+        assert_eq!(Err(ParseError::UnknownInstruction { name: String::from("jmp") }), set.parse("jmp 2"));
+        assert_eq!(
+            Err(ParseError::Operands { name: String::from("addx"), expected: 1, found: 0 }),
+            set.parse("addx")
+        );
+        assert_eq!(Err(ParseError::Operand { text: String::from("x") }), set.parse("addx x"));
+    }
+}
@@ -1,6 +1,9 @@
-use std::{str::FromStr, num::ParseIntError};
+use crate::utils::download_day;
 
-use aoc_downloader::download_day;
+pub mod assembler;
+pub mod cpu;
+
+use cpu::{Cpu, Instruction, InstructionSet, Observer};
 
 const DAY: u32 = 10;
 
@@ -13,45 +16,14 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Input = Instruction;
-type Output = i64;
-
-enum Instruction {
-    Addx(i64),
-    Noop,
-}
-
-impl FromStr for Instruction {
-    type Err = ParseIntError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let noop_re = regex!(r"noop");
-        let add_re = regex!(r"addx (-?\d+)");
-        
-        if noop_re.is_match(s) {
-            Ok(Instruction::Noop)
-        } else {
-            Ok(add_re.captures(s).map(|captured| {
-                Instruction::Addx(captured[1].parse::<i64>().unwrap())
-            })
-            .unwrap())
-        }
-    }
-}
+pub type Input = Instruction;
+pub type Output = i64;
 
-impl Instruction {
-    pub fn cycles(&self) -> usize {
-        match self {
-            Self::Noop => 1,
-            Self::Addx(_) => 2,
-        }
-    }
-}
-
-fn parse_input(input: Vec<String>) -> Vec<Input> {
+pub fn parse_input(input: Vec<String>) -> Vec<Input> {
+    let set = InstructionSet::default();
     input
         .iter()
-        .map(|v| Instruction::from_str(v).unwrap())
+        .map(|v| set.parse(v).unwrap())
         .collect::<Vec<_>>()
 }
 
@@ -61,81 +33,63 @@ pub fn run_day() {
     println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
 }
 
-struct Cpu {
-    pub register: i64,
-    pub clock_count: i64,
-    pub checkpoint: i64,
-    pub checkpoints: Vec<(i64, i64)>,
+// Sums cycle * X during every `every`th cycle, starting with cycle `first`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignalStrength {
+    pub first: u64,
+    pub every: u64,
+    pub total: i64,
 }
 
-enum Symbol {
-    Pixel(char),
-    Eol(char),
+impl Default for SignalStrength {
+    fn default() -> Self {
+        SignalStrength { first: 20, every: 40, total: 0 }
+    }
 }
 
-impl Cpu {
-    pub fn execute_instruction(&mut self, op: &Instruction) {
-        for _ in 0..op.cycles() {
-            self.clock_count += 1;
-            if self.clock_count == self.checkpoint {
-                self.checkpoints.push((self.clock_count, self.register));
-                self.checkpoint += 40;
-            }
+impl Observer for SignalStrength {
+    fn tick(&mut self, cycle: u64, x: i64) {
+        if cycle >= self.first && cycle % self.every == self.first % self.every {
+            self.total += cycle as i64 * x;
         }
-        if let Instruction::Addx(val) = op { self.register += val }
     }
+}
 
-    fn pointer_in_sprite(&self) -> bool {
-        ((self.register - 1)..=(self.register + 1)).contains(&(self.clock_count - 1))
-    }
+pub const CRT_WIDTH: usize = 40;
+pub const CRT_HEIGHT: usize = 6;
 
-    pub fn draw_instruction(&mut self, op: &Instruction) -> Vec<Symbol> {
-        let mut ret = vec![];
-        for _ in 0..op.cycles() {
-            self.clock_count += 1;
-            let pixel = if self.pointer_in_sprite() {
-                '#'
-            } else {
-                '.'
-            };
-            if self.clock_count == self.checkpoint {
-                self.clock_count = 0;
-                ret.push(Symbol::Eol(pixel))
-            } else {
-                ret.push(Symbol::Pixel(pixel))
-            };
-        }
-        if let Instruction::Addx(val) = op { self.register += val }
-        ret
-    }
+// Draws one pixel per cycle, lit when the three pixel wide sprite centered on X covers it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Crt {
+    pub pixels: Vec<bool>,
 }
 
-fn part1(input: &[Input]) -> Output {
-    let mut cpu = Cpu { register: 1, clock_count: 0, checkpoint: 20, checkpoints: vec![] };
-
-    for op in input {
-        cpu.execute_instruction(op);
+impl Observer for Crt {
+    fn tick(&mut self, cycle: u64, x: i64) {
+        let column = (cycle as usize - 1) % CRT_WIDTH;
+        self.pixels.push((x - column as i64).abs() <= 1);
     }
-    cpu.checkpoints.iter().map(|(c, r)| c * r).sum()
 }
 
-fn part2(input: &[Input]) -> String {
-    let mut cpu = Cpu { register: 1, clock_count: 0, checkpoint: 40, checkpoints: vec![] };
+impl Crt {
+    pub fn render(&self) -> String {
+        self.pixels
+            .chunks(CRT_WIDTH)
+            .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).chain(['\n']).collect::<String>())
+            .collect()
+    }
+}
 
-    let mut screen = String::from("\n");
+pub fn part1(input: &[Input]) -> Output {
+    let mut signal = SignalStrength::default();
+    Cpu::new(InstructionSet::default(), input.to_vec()).run(&mut [&mut signal]);
+    signal.total
+}
 
-    for op in input {
-        for symbol in cpu.draw_instruction(op) {
-            match symbol {
-                Symbol::Pixel(px) => screen.push(px),
-                Symbol::Eol(px) => {
-                    screen.push(px);
-                    screen.push('\n');
-                }
-            }
-        }
-    }
-    screen
+pub fn part2(input: &[Input]) -> String {
+    let mut crt = Crt::default();
+    Cpu::new(InstructionSet::default(), input.to_vec()).run(&mut [&mut crt]);
+    format!("\n{}", crt.render())
 }
 
 #[cfg(test)]
@@ -162,4 +116,18 @@ mod tests {
 
         assert_eq!(expected, part2(&input));
     }
+
+    #[test]
+    fn day10_observers() {
+        let input = vec![Instruction::noop(); 240];
+        assert_eq!(720, part1(&input));
+        assert_eq!(format!("\n{}", "###.....................................\n".repeat(6)), part2(&input));
+
+        let input = parse_input(vec!["addx 5".into(), "noop".into(), "addx -6".into()]);
+        let mut signal = SignalStrength { first: 2, every: 2, total: 0 };
+        let mut crt = Crt::default();
+        Cpu::new(InstructionSet::default(), input).run(&mut [&mut signal, &mut crt]);
+        assert_eq!(2 + 4 * 6, signal.total);
+        assert_eq!("##...\n", crt.render());
+    }
 }
@@ -1,4 +1,8 @@
-use aoc_downloader::download_day;
+use crate::utils::download_day;
+
+pub mod simulator;
+
+use simulator::{Relief, Simulator};
 
 const DAY: u32 = 11;
 
@@ -11,182 +15,209 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Input = Monkey;
-type Output = u64;
-
-fn parse_input(_input: Vec<String>) -> Vec<Input> {
-    let common = 7 * 19 * 5 * 11 * 17 * 13 * 2 * 3;
-    vec![
-        Monkey::new(vec![57, 58], 7, m0, (2, 3), common),
-        Monkey::new(vec![66, 52, 59, 79, 94, 73], 19, m1, (4, 6), common),
-        Monkey::new(vec![80], 5, m2, (7, 5), common),
-        Monkey::new(vec![82, 81, 68, 66, 71, 83, 75, 97], 11, m3, (5, 2), common),
-        Monkey::new(vec![55, 52, 67, 70, 69, 94, 90], 17, m4, (0, 3), common),
-        Monkey::new(vec![69, 85, 89, 91], 13, m5, (1, 7), common),
-        Monkey::new(vec![75, 53, 73, 52, 75], 2, m6, (0, 4), common),
-        Monkey::new(vec![94, 60, 79], 3, m7, (1, 6), common),
-    ]
-    /*
-    let common = 23 * 19 * 13 * 17;
-    vec![
-        Monkey::new(vec![79, 98], 23, t0, (2, 3), common),
-        Monkey::new(vec![54, 65, 75, 74], 19, t1, (2, 0), common),
-        Monkey::new(vec![79, 60, 97], 13, t2, (1, 3), common),
-        Monkey::new(vec![74], 17, t3, (0, 1), common),
-    ]
-    */
+pub type Input = Monkey;
+pub type Output = u64;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    Line { line: usize, expected: &'static str },
+    Number { line: usize, text: String },
+    Operation { line: usize, text: String },
+    ZeroDivisor { line: usize },
+    MonkeyId { line: usize, expected: MonkeyId, found: MonkeyId },
+    UnknownTarget { monkey: MonkeyId, target: MonkeyId },
 }
 
-type Op = fn(Item) -> Item;
-
-fn m0(lhs: Item) -> Item {
-    lhs * 19
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Line { line, expected } => write!(f, "Line {line}: expected {expected}"),
+            Self::Number { line, text } => write!(f, "Line {line}: {text} is not a number"),
+            Self::Operation { line, text } => write!(f, "Line {line}: can't read operation {text}"),
+            Self::ZeroDivisor { line } => write!(f, "Line {line}: test divides by zero"),
+            Self::MonkeyId { line, expected, found } => {
+                write!(f, "Line {line}: found monkey {found}, expected {expected}")
+            }
+            Self::UnknownTarget { monkey, target } => {
+                write!(f, "Monkey {monkey} throws to unknown monkey {target}")
+            }
+        }
+    }
 }
 
-fn m1(lhs: Item) -> Item {
-    lhs + 1
+// The right hand side of `new = ...`, `*` binds tighter than `+`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    Old,
+    Value(Item),
+    Add(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
 }
 
-fn m2(lhs: Item) -> Item {
-    lhs + 6
-}
+impl Expr {
+    pub fn eval(&self, old: Item) -> Item {
+        match self {
+            Self::Old => old,
+            Self::Value(value) => *value,
+            Self::Add(lhs, rhs) => lhs.eval(old) + rhs.eval(old),
+            Self::Mul(lhs, rhs) => lhs.eval(old) * rhs.eval(old),
+        }
+    }
 
-fn m3(lhs: Item) -> Item {
-    lhs + 5
+    pub fn parse(text: &str) -> Option<Self> {
+        let tokens = text.split_whitespace().collect::<Vec<_>>();
+        let mut sum = None;
+        for term in tokens.split(|&token| token == "+") {
+            let mut product = None;
+            for factor in term.split(|&token| token == "*") {
+                let factor = match factor {
+                    ["old"] => Self::Old,
+                    [value] => Self::Value(value.parse().ok()?),
+                    _ => return None,
+                };
+                product = Some(match product {
+                    Some(lhs) => Self::Mul(Box::new(lhs), Box::new(factor)),
+                    None => factor,
+                });
+            }
+            sum = Some(match sum {
+                Some(lhs) => Self::Add(Box::new(lhs), Box::new(product?)),
+                None => product?,
+            });
+        }
+        sum
+    }
 }
 
-fn m4(lhs: Item) -> Item {
-    lhs * lhs
+impl std::fmt::Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Old => write!(f, "old"),
+            Self::Value(value) => write!(f, "{value}"),
+            Self::Add(lhs, rhs) => write!(f, "{lhs} + {rhs}"),
+            Self::Mul(lhs, rhs) => write!(f, "{lhs} * {rhs}"),
+        }
+    }
 }
 
-fn m5(lhs: Item) -> Item {
-    lhs + 7
+fn gcd(a: Item, b: Item) -> Item {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
 }
 
-fn m6(lhs: Item) -> Item {
-    lhs * 7
+// Worry levels can be kept modulo this without changing the outcome of any test.
+pub fn common_modulus(monkeys: &[Monkey]) -> Item {
+    monkeys.iter().fold(1, |lcm, monkey| lcm / gcd(lcm, monkey.test_val) * monkey.test_val)
 }
 
-fn m7(lhs: Item) -> Item {
-    lhs + 2
+fn field<'a>(lines: &[&'a str], idx: usize, prefix: &'static str) -> Result<&'a str, ParseError> {
+    lines
+        .get(idx)
+        .and_then(|line| line.trim().strip_prefix(prefix))
+        .ok_or(ParseError::Line { line: idx + 1, expected: prefix })
 }
 
-#[allow(dead_code)]
-fn t0(lhs: Item) -> Item {
-    lhs * 19
+fn number<T: std::str::FromStr>(text: &str, idx: usize) -> Result<T, ParseError> {
+    text.trim().parse().map_err(|_| ParseError::Number { line: idx + 1, text: text.trim().to_string() })
 }
 
-#[allow(dead_code)]
-fn t1(lhs: Item) -> Item {
-    lhs + 6
-}
+pub fn parse_notes(input: &[String]) -> Result<Vec<Monkey>, ParseError> {
+    let lines = input.iter().map(String::as_str).collect::<Vec<_>>();
+    let mut monkeys = vec![];
+    let mut idx = 0;
+    while idx < lines.len() {
+        if lines[idx].trim().is_empty() {
+            idx += 1;
+            continue;
+        }
+        let id = field(&lines, idx, "Monkey ")?;
+        let id = number::<MonkeyId>(id.strip_suffix(':').unwrap_or(id), idx)?;
+        if id != monkeys.len() {
+            return Err(ParseError::MonkeyId { line: idx + 1, expected: monkeys.len(), found: id });
+        }
+        let items = field(&lines, idx + 1, "Starting items:")?;
+        let items = items
+            .split(',')
+            .filter(|item| !item.trim().is_empty())
+            .map(|item| number(item, idx + 1))
+            .collect::<Result<Vec<_>, _>>()?;
+        let operation = field(&lines, idx + 2, "Operation: new =")?;
+        let operation = Expr::parse(operation).ok_or_else(|| ParseError::Operation {
+            line: idx + 3,
+            text: operation.trim().to_string(),
+        })?;
+        let test_val = number(field(&lines, idx + 3, "Test: divisible by")?, idx + 3)?;
+        if test_val == 0 {
+            return Err(ParseError::ZeroDivisor { line: idx + 4 });
+        }
+        let if_true = number(field(&lines, idx + 4, "If true: throw to monkey")?, idx + 4)?;
+        let if_false = number(field(&lines, idx + 5, "If false: throw to monkey")?, idx + 5)?;
+        monkeys.push(Monkey::new(items, test_val, operation, (if_true, if_false)));
+        idx += 6;
+    }
 
-#[allow(dead_code)]
-fn t2(lhs: Item) -> Item {
-    lhs * lhs
+    for (id, monkey) in monkeys.iter().enumerate() {
+        let (if_true, if_false) = monkey.targets;
+        if let Some(&target) = [if_true, if_false].iter().find(|&&target| target >= monkeys.len()) {
+            return Err(ParseError::UnknownTarget { monkey: id, target });
+        }
+    }
+    Ok(monkeys)
 }
 
-#[allow(dead_code)]
-fn t3(lhs: Item) -> Item {
-    lhs + 3
+pub fn parse_input(input: Vec<String>) -> Vec<Input> {
+    parse_notes(&input).unwrap_or_else(|e| panic!("Invalid monkey notes: {e}"))
 }
 
-type Item = u64;
-type MonkeyId = usize;
-type Throw = (MonkeyId, Item);
-
-#[derive(Clone)]
-struct Monkey {
-    items: Vec<Item>,
-    test_val: Item,
-    inspect_it: Op,
-    targets: (MonkeyId, MonkeyId),
-    pub inspections: u64,
-    common: Item,
+pub type Item = u64;
+pub type MonkeyId = usize;
+// One monkey as written in the notes, the simulator keeps track of what it holds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Monkey {
+    pub items: Vec<Item>,
+    pub test_val: Item,
+    pub operation: Expr,
+    pub targets: (MonkeyId, MonkeyId),
 }
 
 impl Monkey {
-    pub fn new(starting_items: Vec<Item>, test_val: Item, inspect: Op, targets: (MonkeyId, MonkeyId), common: Item) -> Self {
+    pub fn new(starting_items: Vec<Item>, test_val: Item, operation: Expr, targets: (MonkeyId, MonkeyId)) -> Self {
         Monkey {
             items: starting_items,
             test_val,
-            inspect_it: inspect,
+            operation,
             targets,
-            inspections: 0,
-            common
         }
     }
 
-    fn checkout(&self, item: Item) -> Item {
-        (self.inspect_it)(item)
-    }
-
-    fn unworry(&self, item: Item, worry: Item) -> Item {
-        let item = item / worry;
-        item % self.common
-    }
-
-    fn test(&self, item: Item) -> bool {
-        item % self.test_val == 0
-    }
-
-    pub fn turn(&mut self, worry_factor: Item) -> Vec<Throw> {
-        let mut throws = vec![];
-        while let Some(mut item) = self.items.pop() {
-            item = self.checkout(item);
-            item = self.unworry(item, worry_factor);
-            let target = if self.test(item) {
-                self.targets.0
-            } else {
-                self.targets.1
-            };
-            throws.push((target, item));
-            self.inspections += 1;
+    pub fn target(&self, divisible: bool) -> MonkeyId {
+        if divisible {
+            self.targets.0
+        } else {
+            self.targets.1
         }
-        throws
-    }
-
-    pub fn catch(&mut self, new_item: Item) {
-        self.items.push(new_item)
     }
 }
 
-
 pub fn run_day() {
     let input = get_input();
     let input = parse_input(input);
     println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
 }
 
-fn follow_the_monkey(input: &[Input], round: usize, worry_factor: Item) -> Vec<Input> {
-    let mut input = input.to_owned();
-    for _ in 0..round {
-        for i in 0..input.len() {
-            let air = input[i].turn(worry_factor);
-            for throw in air {
-                input[throw.0].catch(throw.1);
-            }
-        }
-    }
-
-    input.sort_by(|a, b| a.inspections.cmp(&b.inspections));
-    input
+pub fn part1(input: &[Input]) -> Output {
+    let mut simulator = Simulator::<Item>::new(input, Relief::Divide(3)).unwrap();
+    simulator.run(20);
+    simulator.monkey_business()
 }
 
-fn score(input: &[Input]) -> Output {
-    input.iter()
-        .rev()
-        .take(2)
-        .map(|m| m.inspections)
-        .product()
-}
-
-fn part1(input: &[Input]) -> Output {
-    score(&follow_the_monkey(input, 20, 3))
-}
-
-fn part2(input: &[Input]) -> Output {
-    score(&follow_the_monkey(input, 10_000, 1))
+pub fn part2(input: &[Input]) -> Output {
+    let mut simulator = Simulator::<Item>::new(input, Relief::Modulus(common_modulus(input))).unwrap();
+    simulator.run(10_000);
+    simulator.monkey_business()
 }
 
 #[cfg(test)]
@@ -204,4 +235,79 @@ mod tests {
         let input = parse_input(get_input());
         assert_eq!(14399640002, part2(&input));
     }
+
+    pub fn example() -> Vec<String> {
+        "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1"
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn day11_example() {
+        let input = parse_input(example());
+        assert_eq!(4, input.len());
+        assert_eq!(23 * 19 * 13 * 17, common_modulus(&input));
+        assert_eq!(10605, part1(&input));
+        assert_eq!(2713310158, part2(&input));
+    }
+
+    #[test]
+    fn day11_notes_errors() {
+        assert_eq!(Some(Expr::Mul(Box::new(Expr::Old), Box::new(Expr::Old))), Expr::parse("old * old"));
+        let expr = Expr::parse("old + 2 * old").unwrap();
+        assert_eq!("old + 2 * old", expr.to_string());
+        assert_eq!(15, expr.eval(5));
+        assert_eq!(None, Expr::parse("old - 2"));
+        assert_eq!(None, Expr::parse("old +"));
+
+        let mut notes = example();
+        notes[2] = String::from("  Operation: new = old / 2");
+        assert_eq!(
+            Err(ParseError::Operation { line: 3, text: String::from("old / 2") }),
+            parse_notes(&notes)
+        );
+        let mut notes = example();
+        notes[7] = String::from("Monkey 2:");
+        assert_eq!(Err(ParseError::MonkeyId { line: 8, expected: 1, found: 2 }), parse_notes(&notes));
+        let mut notes = example();
+        notes[11] = String::from("    If true: throw to monkey 4");
+        assert_eq!(Err(ParseError::UnknownTarget { monkey: 1, target: 4 }), parse_notes(&notes));
+        assert_eq!(
+            Err(ParseError::Line { line: 4, expected: "Test: divisible by" }),
+            parse_notes(&example()[..3])
+        );
+        let mut notes = example();
+        notes[10] = String::from("  Test: divisible by 0");
+        assert_eq!(Err(ParseError::ZeroDivisor { line: 11 }), parse_notes(&notes));
+        let mut notes = example();
+        notes[1] = String::from("  Starting items: 79, x");
+        assert_eq!(Err(ParseError::Number { line: 2, text: String::from("x") }), parse_notes(&notes));
+    }
 }
@@ -0,0 +1,386 @@
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+use super::{Expr, Item, Monkey, MonkeyId};
+use crate::bigint::BigUint;
+
+// What a worry level has to support. Plain integers are enough with relief, without any they
+// grow without bound and need a `BigUint`.
+pub trait Worry: Clone + Ord + std::fmt::Display {
+    fn from_item(item: Item) -> Self;
+    fn add(&self, other: &Self) -> Self;
+    fn mul(&self, other: &Self) -> Self;
+    fn div(&self, divisor: Item) -> Self;
+    fn rem(&self, divisor: Item) -> Item;
+}
+
+impl Worry for Item {
+    fn from_item(item: Item) -> Self {
+        item
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        self * other
+    }
+
+    fn div(&self, divisor: Item) -> Self {
+        self / divisor
+    }
+
+    fn rem(&self, divisor: Item) -> Item {
+        self % divisor
+    }
+}
+
+impl Worry for BigUint {
+    fn from_item(item: Item) -> Self {
+        BigUint::from(item)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        self * other
+    }
+
+    fn div(&self, divisor: Item) -> Self {
+        self.div_rem(divisor).0
+    }
+
+    fn rem(&self, divisor: Item) -> Item {
+        self.div_rem(divisor).1
+    }
+}
+
+fn eval<W: Worry>(expr: &Expr, old: &W) -> W {
+    match expr {
+        Expr::Old => old.clone(),
+        Expr::Value(value) => W::from_item(*value),
+        Expr::Add(lhs, rhs) => eval(lhs, old).add(&eval(rhs, old)),
+        Expr::Mul(lhs, rhs) => eval(lhs, old).mul(&eval(rhs, old)),
+    }
+}
+
+// How worry levels are kept in check after every inspection. `Modulus` should be a multiple of
+// every test, like `common_modulus`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relief {
+    Divide(Item),
+    Modulus(Item),
+    None,
+}
+
+impl Relief {
+    fn apply<W: Worry>(&self, worry: W) -> W {
+        match *self {
+            Self::Divide(divisor) => worry.div(divisor),
+            Self::Modulus(modulus) => W::from_item(worry.rem(modulus)),
+            Self::None => worry,
+        }
+    }
+}
+
+// Notes built by hand get the checks `parse_notes` does, relief has to divide by something too.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimulatorError {
+    ZeroRelief,
+    ZeroDivisor { monkey: MonkeyId },
+    UnknownTarget { monkey: MonkeyId, target: MonkeyId },
+}
+
+impl std::fmt::Display for SimulatorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::ZeroRelief => write!(f, "Relief divides by zero"),
+            Self::ZeroDivisor { monkey } => write!(f, "Monkey {monkey} tests by dividing by zero"),
+            Self::UnknownTarget { monkey, target } => {
+                write!(f, "Monkey {monkey} throws to unknown monkey {target}")
+            }
+        }
+    }
+}
+
+// The lowest and highest worry level held at the end of a round.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorryStats<W> {
+    pub min: W,
+    pub max: W,
+}
+
+// An item in flight, numbered in the order of the notes.
+pub type ItemId = usize;
+
+// Plays the monkeys' game. Items are inspected in the order they were caught. Besides the totals
+// every round's inspections, where each item went and the range of worry levels are recorded,
+// and with `trace` the whole game is written out like in the puzzle.
+#[derive(Clone, Debug)]
+pub struct Simulator<W> {
+    pub monkeys: Vec<Monkey>,
+    pub relief: Relief,
+    holding: Vec<VecDeque<(ItemId, W)>>,
+    pub round: usize,
+    pub inspections: Vec<u64>,
+    // Inspections per monkey during every round.
+    pub history: Vec<Vec<u64>>,
+    // For every item the rounds it was thrown in and who caught it, starting with round 0.
+    pub timelines: Vec<Vec<(usize, MonkeyId)>>,
+    pub stats: Vec<Option<WorryStats<W>>>,
+    pub trace: Option<String>,
+}
+
+impl<W: Worry> Simulator<W> {
+    pub fn new(monkeys: &[Monkey], relief: Relief) -> Result<Self, SimulatorError> {
+        if matches!(relief, Relief::Divide(0) | Relief::Modulus(0)) {
+            return Err(SimulatorError::ZeroRelief);
+        }
+        for (id, monkey) in monkeys.iter().enumerate() {
+            if monkey.test_val == 0 {
+                return Err(SimulatorError::ZeroDivisor { monkey: id });
+            }
+            let (if_true, if_false) = monkey.targets;
+            if let Some(target) = [if_true, if_false].into_iter().find(|&target| target >= monkeys.len()) {
+                return Err(SimulatorError::UnknownTarget { monkey: id, target });
+            }
+        }
+        let mut holding = vec![VecDeque::new(); monkeys.len()];
+        let mut timelines = vec![];
+        for (id, monkey) in monkeys.iter().enumerate() {
+            for &item in &monkey.items {
+                holding[id].push_back((timelines.len(), W::from_item(item)));
+                timelines.push(vec![(0, id)]);
+            }
+        }
+        Ok(Simulator {
+            monkeys: monkeys.to_vec(),
+            relief,
+            holding,
+            round: 0,
+            inspections: vec![0; monkeys.len()],
+            history: vec![],
+            timelines,
+            stats: vec![],
+            trace: None,
+        })
+    }
+
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(String::new());
+        self
+    }
+
+    fn log(&mut self, line: std::fmt::Arguments) {
+        if let Some(trace) = self.trace.as_mut() {
+            trace.write_fmt(line).unwrap();
+            trace.push('\n');
+        }
+    }
+
+    // Writes one inspection like the puzzle does: the worry level before, after the operation and
+    // after relief, the test and the throw.
+    fn log_inspection(&mut self, monkey: &Monkey, old: &W, inspected: &W, worry: &W, divisible: bool) {
+        self.log(format_args!("  Monkey inspects an item with a worry level of {old}."));
+        match &monkey.operation {
+            Expr::Mul(lhs, rhs) if matches!((&**lhs, &**rhs), (Expr::Old, Expr::Old)) => {
+                self.log(format_args!("    Worry level is multiplied by itself to {inspected}."))
+            }
+            Expr::Add(lhs, rhs) if matches!((&**lhs, &**rhs), (Expr::Old, Expr::Old)) => {
+                self.log(format_args!("    Worry level increases by itself to {inspected}."))
+            }
+            Expr::Mul(lhs, rhs) if matches!(**lhs, Expr::Old) => {
+                self.log(format_args!("    Worry level is multiplied by {rhs} to {inspected}."))
+            }
+            Expr::Add(lhs, rhs) if matches!(**lhs, Expr::Old) => {
+                self.log(format_args!("    Worry level increases by {rhs} to {inspected}."))
+            }
+            operation => self.log(format_args!("    Worry level becomes {operation} = {inspected}.")),
+        }
+        match self.relief {
+            Relief::Divide(divisor) => self.log(format_args!(
+                "    Monkey gets bored with item. Worry level is divided by {divisor} to {worry}."
+            )),
+            Relief::Modulus(modulus) => self.log(format_args!("    Worry level is kept modulo {modulus} to {worry}.")),
+            Relief::None => (),
+        }
+        let not = if divisible { "" } else { "not " };
+        let target = monkey.target(divisible);
+        self.log(format_args!("    Current worry level is {not}divisible by {}.", monkey.test_val));
+        self.log(format_args!("    Item with worry level {worry} is thrown to monkey {target}."));
+    }
+
+    pub fn run_round(&mut self) {
+        self.round += 1;
+        // The notes are only read, taking them out for the round saves cloning each monkey.
+        let monkeys = std::mem::take(&mut self.monkeys);
+        let mut inspected = vec![];
+        for (id, monkey) in monkeys.iter().enumerate() {
+            let mut count = 0;
+            self.log(format_args!("Monkey {id}:"));
+            while let Some((item, old)) = self.holding[id].pop_front() {
+                let worry = eval(&monkey.operation, &old);
+                let (worry, before_relief) = match self.trace {
+                    Some(_) => (self.relief.apply(worry.clone()), Some(worry)),
+                    None => (self.relief.apply(worry), None),
+                };
+                let divisible = worry.rem(monkey.test_val) == 0;
+                let target = monkey.target(divisible);
+                if let Some(before_relief) = before_relief {
+                    self.log_inspection(monkey, &old, &before_relief, &worry, divisible);
+                }
+                self.timelines[item].push((self.round, target));
+                self.holding[target].push_back((item, worry));
+                count += 1;
+            }
+            inspected.push(count);
+        }
+        self.monkeys = monkeys;
+        self.inspections.iter_mut().zip(&inspected).for_each(|(total, round)| *total += round);
+        self.history.push(inspected);
+        let worries = self.holding.iter().flatten().map(|(_, worry)| worry);
+        self.stats.push(match (worries.clone().min(), worries.max()) {
+            (Some(min), Some(max)) => Some(WorryStats { min: min.clone(), max: max.clone() }),
+            _ => None,
+        });
+    }
+
+    pub fn run(&mut self, rounds: usize) {
+        (0..rounds).for_each(|_| self.run_round());
+    }
+
+    // The worry levels every monkey holds, in the order it will inspect them.
+    pub fn holdings(&self) -> Vec<Vec<W>> {
+        self.holding
+            .iter()
+            .map(|items| items.iter().map(|(_, worry)| worry.clone()).collect())
+            .collect()
+    }
+
+    // Who held `item` at the end of `round`.
+    pub fn owner(&self, item: ItemId, round: usize) -> MonkeyId {
+        self.timelines[item]
+            .iter()
+            .take_while(|&&(thrown, _)| thrown <= round)
+            .last()
+            .unwrap()
+            .1
+    }
+
+    pub fn monkey_business(&self) -> u64 {
+        let mut inspections = self.inspections.clone();
+        inspections.sort_unstable_by(|a, b| b.cmp(a));
+        inspections.iter().take(2).product()
+    }
+
+    pub fn holdings_summary(&self) -> String {
+        let mut out = format!(
+            "After round {}, the monkeys are holding items with these worry levels:\n",
+            self.round
+        );
+        for (id, items) in self.holdings().iter().enumerate() {
+            let items = items.iter().map(|worry| worry.to_string()).collect::<Vec<_>>();
+            writeln!(out, "{}", format!("Monkey {id}: {}", items.join(", ")).trim_end()).unwrap();
+        }
+        out
+    }
+
+    pub fn inspections_summary(&self) -> String {
+        let mut out = format!("== After round {} ==\n", self.round);
+        for (id, inspections) in self.inspections.iter().enumerate() {
+            writeln!(out, "Monkey {id} inspected items {inspections} times.").unwrap();
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{common_modulus, parse_input, tests::example};
+    use super::*;
+
+    #[test]
+    fn day11_trace() {
+        let monkeys = parse_input(example());
+        let mut simulator = Simulator::<Item>::new(&monkeys, Relief::Divide(3)).unwrap().with_trace();
+        simulator.run_round();
+        let trace = simulator.trace.as_ref().unwrap();
+        let expected = "\
+Monkey 0:
+  Monkey inspects an item with a worry level of 79.
+    Worry level is multiplied by 19 to 1501.
+    Monkey gets bored with item. Worry level is divided by 3 to 500.
+    Current worry level is not divisible by 23.
+    Item with worry level 500 is thrown to monkey 3.
+  Monkey inspects an item with a worry level of 98.
+";
+        assert!(trace.starts_with(expected));
+        assert!(trace.contains("    Worry level is multiplied by itself to 6241.\n"));
+        assert!(trace.contains("    Worry level increases by 6 to 60.\n"));
+        assert_eq!(
+            "\
+After round 1, the monkeys are holding items with these worry levels:
+Monkey 0: 20, 23, 27, 26
+Monkey 1: 2080, 25, 167, 207, 401, 1046
+Monkey 2:
+Monkey 3:
+",
+            simulator.holdings_summary()
+        );
+        assert_eq!(vec![vec![2, 4, 3, 5]], simulator.history);
+        assert_eq!(vec![(0, 0), (1, 3), (1, 1)], simulator.timelines[0]);
+        assert_eq!((0, 1), (simulator.owner(0, 0), simulator.owner(2, 0)));
+        assert_eq!(1, simulator.owner(0, 1));
+        assert_eq!(Some(WorryStats { min: 20, max: 2080 }), simulator.stats[0]);
+
+        simulator.run(19);
+        assert_eq!(vec![101, 95, 7, 105], simulator.inspections);
+        assert_eq!(10605, simulator.monkey_business());
+        // Both throws going to the same monkey still trace the test's real outcome.
+        let mut notes = example();
+        notes[4] = String::from("    If true: throw to monkey 3");
+        let mut simulator = Simulator::<Item>::new(&parse_input(notes), Relief::Divide(3)).unwrap().with_trace();
+        simulator.run_round();
+        let trace = simulator.trace.unwrap();
+        assert!(trace.contains("    Current worry level is not divisible by 23.\n"));
+    }
+
+    #[test]
+    fn day11_relief() {
+        let monkeys = parse_input(example());
+        let mut modulus = Simulator::<Item>::new(&monkeys, Relief::Modulus(common_modulus(&monkeys))).unwrap();
+        modulus.run(1);
+        assert_eq!(
+            "== After round 1 ==\n\
+             Monkey 0 inspected items 2 times.\n\
+             Monkey 1 inspected items 4 times.\n\
+             Monkey 2 inspected items 3 times.\n\
+             Monkey 3 inspected items 6 times.\n",
+            modulus.inspections_summary()
+        );
+
+        // Without relief the worry levels explode, but every test still comes out the same.
+        let mut unbounded = Simulator::<BigUint>::new(&monkeys, Relief::None).unwrap();
+        unbounded.run(20);
+        modulus.run(19);
+        assert_eq!(modulus.history, unbounded.history);
+        assert_eq!(modulus.timelines, unbounded.timelines);
+        let max = unbounded.stats[19].as_ref().unwrap().max.bits();
+        assert!(max > 64, "{max} bits");
+    }
+
+    #[test]
+    fn day11_simulator_errors() {
+        let mut monkeys = parse_input(example());
+        let new = |monkeys: &[Monkey], relief| Simulator::<Item>::new(monkeys, relief).map(|_| ());
+        assert_eq!(Err(SimulatorError::ZeroRelief), new(&monkeys, Relief::Divide(0)));
+        assert_eq!(Err(SimulatorError::ZeroRelief), new(&monkeys, Relief::Modulus(0)));
+        monkeys[2].targets.1 = 4;
+        assert_eq!(Err(SimulatorError::UnknownTarget { monkey: 2, target: 4 }), new(&monkeys, Relief::None));
+        monkeys[1].test_val = 0;
+        assert_eq!(Err(SimulatorError::ZeroDivisor { monkey: 1 }), new(&monkeys, Relief::None));
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::{HashSet, VecDeque};
 
-use aoc_downloader::download_day;
+use crate::utils::download_day;
 
 const DAY: u32 = 12;
 
@@ -13,10 +13,10 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Input = Vec<char>;
-type Output = u64;
+pub type Input = Vec<char>;
+pub type Output = u64;
 
-fn parse_input(input: Vec<String>) -> Vec<Input> {
+pub fn parse_input(input: Vec<String>) -> Vec<Input> {
     input
         .iter()
         .map(|l| l.chars().collect())
@@ -85,14 +85,14 @@ fn traverse_bf(input: &[Input], start: Coord, end: char, valid_check: CheckValid
     }
 }
 
-fn part1(input: &[Input]) -> Output {
+pub fn part1(input: &[Input]) -> Output {
     let (start, _) = find_start_end(input);
     let mut input = input.to_owned();
     input[start.unwrap().1][start.unwrap().0] = 'a';
     traverse_bf(&input, start.unwrap(), 'E', goes_up).unwrap()
 }
 
-fn part2(input: &[Input]) -> Output {
+pub fn part2(input: &[Input]) -> Output {
     let (start, end) = find_start_end(input);
     let mut input = input.to_owned();
     input[start.unwrap().1][start.unwrap().0] = 'a';
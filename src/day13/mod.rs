@@ -1,7 +1,7 @@
 use std::{cmp::Ordering, num::ParseIntError};
 
 
-use aoc_downloader::download_day;
+use crate::utils::download_day;
 
 const DAY: u32 = 13;
 
@@ -14,10 +14,10 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Input = Side;
-type Output = usize;
+pub type Input = Side;
+pub type Output = usize;
 
-fn parse_input(input: Vec<String>) -> Vec<Input> {
+pub fn parse_input(input: Vec<String>) -> Vec<Input> {
     input
         .iter()
         .filter(|&line| !line.is_empty())
@@ -30,13 +30,13 @@ fn parse_input(input: Vec<String>) -> Vec<Input> {
 
 #[allow(clippy::derive_ord_xor_partial_ord)]
 #[derive(Debug, Ord, Eq, Clone)]
-enum Side {
+pub enum Side {
     Integer(u64),
     List(Vec<Side>),
 }
 
 impl Side {
-    fn from_str_fixed(s: &str) -> Result<(Self, usize), ParseIntError> {
+    pub fn from_str_fixed(s: &str) -> Result<(Self, usize), ParseIntError> {
         let mut stack = String::new();
         let mut list = vec![];
         let mut advance = 0;
@@ -104,7 +104,7 @@ pub fn run_day() {
     println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
 }
 
-fn part1(input: &[Input]) -> Output {
+pub fn part1(input: &[Input]) -> Output {
     let mut index_sum = 0;
     for (i, pair) in input.chunks(2).enumerate() {
         if pair[0] < pair[1] {
@@ -114,7 +114,7 @@ fn part1(input: &[Input]) -> Output {
     index_sum
 }
 
-fn part2(input: &[Input]) -> Output {
+pub fn part2(input: &[Input]) -> Output {
     let mut input = input.to_owned();
     let div_package_1 = Side::List(vec![Side::List(vec![Side::Integer(2)])]);
     let div_package_2 = Side::List(vec![Side::List(vec![Side::Integer(6)])]);
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::cmp::Ordering::*;
 
-use aoc_downloader::download_day;
+use crate::utils::download_day;
 
 const DAY: u32 = 14;
 
@@ -14,7 +14,7 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Output = u32;
+pub type Output = u32;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Cave {
@@ -22,7 +22,7 @@ pub enum Cave {
     Rock,
 }
 
-fn parse_input(input: Vec<String>) -> HashMap<(i32, i32), Cave> {
+pub fn parse_input(input: Vec<String>) -> HashMap<(i32, i32), Cave> {
     let re = regex!(r"(\d+),(\d+)");
     let mut cave_map = HashMap::new();
     for line in input{
@@ -120,7 +120,7 @@ fn rain_sand(cave_map: &mut HashMap<(i32, i32), Cave>, max_y: i32) -> bool {
     true
 }
 
-fn part1(input: &HashMap<(i32, i32), Cave>) -> Output {
+pub fn part1(input: &HashMap<(i32, i32), Cave>) -> Output {
     let mut sand_volume = 0;
     let max_y = *input.keys()
         .map(|(_, y)| y)
@@ -134,7 +134,7 @@ fn part1(input: &HashMap<(i32, i32), Cave>) -> Output {
     sand_volume
 }
 
-fn part2(input: &HashMap<(i32, i32), Cave>) -> Output {
+pub fn part2(input: &HashMap<(i32, i32), Cave>) -> Output {
     const FLOOR_OFFSET: i32  = 2;
     let mut sand_volume = 0;
     let max_y = *input.keys()
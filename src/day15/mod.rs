@@ -2,7 +2,7 @@ use std::ops::{Generator, GeneratorState};
 use std::pin::Pin;
 use std::{str::FromStr, num::ParseIntError};
 
-use aoc_downloader::download_day;
+use crate::utils::download_day;
 
 const DAY: u32 = 15;
 
@@ -15,16 +15,16 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Input = Sensor;
-type Output = u64;
+pub type Input = Sensor;
+pub type Output = u64;
 
-struct Sensor {
+pub struct Sensor {
     pub pos: (isize, isize),
     pub closest_beacon: (isize, isize),
     pub ex_range: isize,
 }
 
-fn manhatten_distance(pos_a: (isize, isize), pos_b: (isize, isize)) -> isize {
+pub fn manhatten_distance(pos_a: (isize, isize), pos_b: (isize, isize)) -> isize {
         (pos_a.0 - pos_b.0).abs() + (pos_a.1 - pos_b.1).abs()
 }
 
@@ -65,7 +65,7 @@ impl FromStr for Sensor {
 }
 
 
-fn parse_input(input: Vec<String>) -> Vec<Input> {
+pub fn parse_input(input: Vec<String>) -> Vec<Input> {
     input
         .iter()
         .map(|line| Sensor::from_str(line).unwrap())
@@ -78,7 +78,7 @@ pub fn run_day() {
     println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
 }
 
-fn part1(input: &[Input]) -> Output {
+pub fn part1(input: &[Input]) -> Output {
     let min_x = input
         .iter()
         .map(|sensor| {
@@ -124,7 +124,7 @@ fn not_in_range(pos: (isize, isize), max_range: isize) -> bool {
     pos.0 < 0 || pos.0 > max_range || pos.1 < 0 || pos.1 > max_range 
 }
 
-fn part2(input: &[Input]) -> Output {
+pub fn part2(input: &[Input]) -> Output {
     let size = 4000000;
     for sensor in input {
         let mut generator = move || {
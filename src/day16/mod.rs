@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 use std::cmp::max;
 
-use aoc_downloader::download_day;
+use crate::memo::Memo;
+use crate::stats;
+
+use crate::utils::download_day;
 
 const DAY: u32 = 16;
 
@@ -14,18 +17,18 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Input = Cache;
-type Output = usize;
+pub type Input = Valves;
+pub type Output = usize;
+
+pub type FlowMemo = Memo<(usize, u64, usize, bool), usize>;
 
-#[derive(Clone)]
-struct Cache {
+pub struct Valves {
     valve_to_idx: HashMap<String, usize>,
     flow_rates: HashMap<usize, usize>,
     successors: HashMap<usize, Vec<usize>>,
-    cache: HashMap<(usize, u64, usize, bool), usize>
 }
 
-fn parse_input(input: Vec<String>) -> Input {
+pub fn parse_input(input: Vec<String>) -> Input {
     let re = regex!(r"Valve (\w+) has flow rate=(\d+); tunnels? leads? to valves? (.*)");
     let mut valve_to_idx = HashMap::new();
     let mut flow_rates = HashMap::new();
@@ -54,11 +57,10 @@ fn parse_input(input: Vec<String>) -> Input {
                     );
             }
         });
-    Cache {
+    Valves {
         valve_to_idx,
         flow_rates,
         successors,
-        cache: HashMap::new(),
     }
 
 }
@@ -69,19 +71,19 @@ pub fn run_day() {
     println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
 }
 
-impl Cache {
-    fn compute_max_flow(&mut self, position: usize, visited: u64, time: usize, part2: bool) -> Output {
+impl Valves {
+    pub fn compute_max_flow(&self, memo: &mut FlowMemo, position: usize, visited: u64, time: usize, part2: bool) -> Output {
         if time == 0 {
             if part2 {
-                return self.compute_max_flow(self.valve_to_idx["AA"], visited, 26, false);
+                return self.compute_max_flow(memo, self.valve_to_idx["AA"], visited, 26, false);
             } else {
                 return 0;
             }
         }
 
         let cache_key = (position, visited, time, part2);
-        if let Some(result) = self.cache.get(&cache_key) {
-            return *result;
+        if let Some(result) = memo.get(&cache_key) {
+            return result;
         }
 
         let mut result = 0;
@@ -92,29 +94,41 @@ impl Cache {
         let not_visited = 0 == (visited & visited_mask);
         if not_visited && flow_rate > 0 {
             let new_visited = visited | visited_mask;
-            result = max(result, (time - 1) * flow_rate + self.compute_max_flow(position, new_visited, time - 1, part2));
+            result = max(result, (time - 1) * flow_rate + self.compute_max_flow(memo, position, new_visited, time - 1, part2));
         }
 
-        for successor in self.successors[&position].clone() {
-            result = max(result, self.compute_max_flow(successor, visited, time - 1, part2));
+        for &successor in &self.successors[&position] {
+            result = max(result, self.compute_max_flow(memo, successor, visited, time - 1, part2));
         }
 
-        self.cache.insert(cache_key, result);
+        memo.insert(cache_key, result);
 
         result
     }
 }
 
-fn part1(input: &Input) -> Output {
-    let mut cache = (*input).clone();
-    let idx_aa = cache.valve_to_idx["AA"];
-    cache.compute_max_flow(idx_aa, 0, 30, false)
+fn report(part: &str, memo: &FlowMemo) {
+    let memo_stats = memo.stats();
+    stats::count(DAY, &format!("{part} states"), memo_stats.hits + memo_stats.misses);
+    stats::memo(DAY, part, &memo_stats);
 }
 
-fn part2(input: &Input) -> Output {
-    let mut cache = (*input).clone();
-    let idx_aa = cache.valve_to_idx["AA"];
-    cache.compute_max_flow(idx_aa, 0, 26, true)
+pub fn part1(input: &Input) -> Output {
+    let _phase = stats::phase(DAY, "part1");
+    let mut memo = FlowMemo::new();
+    let idx_aa = input.valve_to_idx["AA"];
+    let flow = input.compute_max_flow(&mut memo, idx_aa, 0, 30, false);
+    report("part1", &memo);
+    flow
+}
+
+pub fn part2(input: &Input) -> Output {
+    let _phase = stats::phase(DAY, "part2");
+    let mut memo = FlowMemo::new();
+    let idx_aa = input.valve_to_idx["AA"];
+    let flow = input.compute_max_flow(&mut memo, idx_aa, 0, 26, true);
+    report("part2", &memo);
+    flow
 }
 
 #[cfg(test)]
@@ -132,4 +146,29 @@ mod tests {
         let input = parse_input(get_input());
         assert_eq!(2602, part2(&input));
     }
+
+    fn example() -> Vec<String> {
+        [
+            "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB",
+            "Valve BB has flow rate=13; tunnels lead to valves CC, AA",
+            "Valve CC has flow rate=2; tunnels lead to valves DD, BB",
+            "Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE",
+            "Valve EE has flow rate=3; tunnels lead to valves FF, DD",
+            "Valve FF has flow rate=0; tunnels lead to valves EE, GG",
+            "Valve GG has flow rate=0; tunnels lead to valves FF, HH",
+            "Valve HH has flow rate=22; tunnel leads to valve GG",
+            "Valve II has flow rate=0; tunnels lead to valves AA, JJ",
+            "Valve JJ has flow rate=21; tunnel leads to valve II",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn day16_example() {
+        let input = parse_input(example());
+        assert_eq!(1651, part1(&input));
+        assert_eq!(1707, part2(&input));
+    }
 }
@@ -1,4 +1,4 @@
-use aoc_downloader::download_day;
+use crate::utils::download_day;
 use std::collections::HashSet;
 
 const DAY: u32 = 17;
@@ -12,11 +12,11 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Input = Jet;
-type Output = i128;
+pub type Input = Jet;
+pub type Output = i128;
 
 #[derive(Clone, Copy, Debug)]
-enum Jet {
+pub enum Jet {
     Left,
     Right,
 }
@@ -47,7 +47,7 @@ impl Rock {
     }
 }
 
-fn parse_input(input: Vec<String>) -> Vec<Input> {
+pub fn parse_input(input: Vec<String>) -> Vec<Input> {
     input[0]
         .chars()
         .map(|v| if v == '<' { Jet::Left } else { Jet::Right })
@@ -85,7 +85,7 @@ fn print_tetris(tetris: &HashSet<(i128, i128)>) {
 }
 
 
-fn part1(input: &[Input]) -> Output {
+pub fn part1(input: &[Input]) -> Output {
     create_pattern(2022, input).iter().sum()
 }
 
@@ -143,7 +143,7 @@ fn create_pattern(rounds: usize, input: &[Input]) -> Vec<i128> {
     deltas
 }
 
-fn part2(input: &[Input]) -> Output {
+pub fn part2(input: &[Input]) -> Output {
     let sample = 5000;
     let deltas = create_pattern(sample, input);
     let mut found_offset = None;
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 use once_cell::unsync::Lazy;
-use aoc_downloader::download_day;
+use crate::utils::download_day;
 
 const DAY: u32 = 18;
 
@@ -13,10 +13,10 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Input = Vec<i64>;
-type Output = u64;
+pub type Input = Vec<i64>;
+pub type Output = u64;
 
-fn parse_input(input: Vec<String>) -> Vec<Input> {
+pub fn parse_input(input: Vec<String>) -> Vec<Input> {
     input
         .iter()
         .map(|line| line.split(',').map(|v| v.parse::<i64>().unwrap()).collect())
@@ -46,7 +46,7 @@ fn count_neighbours(grid: &HashSet<(i64, i64, i64)>, pos: (i64, i64, i64)) -> u6
         .count() as u64
 }
 
-fn part1(input: &[Input]) -> Output {
+pub fn part1(input: &[Input]) -> Output {
     let mut exposed = HashSet::new();
     for cube in input {
         exposed.insert((cube[0], cube[1], cube[2]));
@@ -93,7 +93,7 @@ fn is_external(external: &mut HashSet<(i64, i64, i64)>, grid: &HashSet<(i64, i64
     false
 }
 
-fn part2(input: &[Input]) -> Output {
+pub fn part2(input: &[Input]) -> Output {
     let mut exposed = HashSet::new();
     for cube in input {
         exposed.insert((cube[0], cube[1], cube[2]));
@@ -2,8 +2,11 @@ use std::str::FromStr;
 use std::num::ParseIntError;
 use rayon::prelude::*;
 
+use crate::memo::Memo;
+use crate::stats;
 
-use aoc_downloader::download_day;
+
+use crate::utils::download_day;
 
 const DAY: u32 = 19;
 
@@ -16,10 +19,10 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Input = Blueprint;
-type Output = u64;
+pub type Input = Blueprint;
+pub type Output = u64;
 
-fn parse_input(input: Vec<String>) -> Vec<Input> {
+pub fn parse_input(input: Vec<String>) -> Vec<Input> {
     input
         .iter()
         .map(|v| Blueprint::from_str(v).unwrap())
@@ -33,12 +36,12 @@ pub fn run_day() {
 }
 
 #[derive(Debug)]
-struct Blueprint {
-    id: u64,
-    ore: Resources,
-    clay: Resources,
-    obsidian: Resources,
-    geode: Resources,
+pub struct Blueprint {
+    pub id: u64,
+    pub ore: Resources,
+    pub clay: Resources,
+    pub obsidian: Resources,
+    pub geode: Resources,
 }
 
 impl FromStr for Blueprint {
@@ -79,27 +82,42 @@ impl FromStr for Blueprint {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
-struct Resources {
-    ore: u64,
-    clay: u64,
-    obsidian: u64,
-    geode: u64,
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Resources {
+    pub ore: u64,
+    pub clay: u64,
+    pub obsidian: u64,
+    pub geode: u64,
 }
 
-#[derive(Clone, Copy, Debug)]
-struct Robots {
-    ore: u64,
-    clay: u64,
-    obsidian: u64,
-    geode: u64,
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Robots {
+    pub ore: u64,
+    pub clay: u64,
+    pub obsidian: u64,
+    pub geode: u64,
 }
 
-fn mine(blueprint: &Blueprint, mut time: u64, mut res: Resources, rob: Robots, best_so_far: u64) -> u64 {
+// Maps a state to the result of searching it and the best score it was searched against.
+pub type MineMemo = Memo<(u64, Resources, Robots), (u64, u64)>;
+
+// Keeps each per-blueprint table small, the searches run in parallel.
+const MEMO_BOUND: usize = 1 << 18;
+
+pub fn mine(blueprint: &Blueprint, memo: &mut MineMemo, mut time: u64, mut res: Resources, rob: Robots, best_so_far: u64) -> u64 {
     if time == 0 {
         return res.geode;
     }
 
+    // A result above the bound it was searched against is exact. Otherwise we only know that this
+    // state can not beat that bound, which is enough if our current best is at least as high.
+    let key = (time, res, rob);
+    if let Some((result, bound)) = memo.get(&key) {
+        if result > bound || bound <= best_so_far {
+            return result;
+        }
+    }
+
     // Check wether with our current robots and by creating a robot per minute we could beat our
     // best. This could be improved, as we probably can not create a robot each minute!
     if res.geode + time * rob.geode + time * (time - 1) / 2 <= best_so_far {
@@ -127,7 +145,7 @@ fn mine(blueprint: &Blueprint, mut time: u64, mut res: Resources, rob: Robots, b
         new_res.obsidian -= blueprint.geode.obsidian;
         let mut new_rob = rob;
         new_rob.geode += 1;
-        max = max.max(mine(blueprint, time, new_res, new_rob, best_so_far));
+        max = max.max(mine(blueprint, memo, time, new_res, new_rob, best_so_far));
     } 
     if new_obsidian {
         let mut new_res = res;
@@ -135,27 +153,35 @@ fn mine(blueprint: &Blueprint, mut time: u64, mut res: Resources, rob: Robots, b
         new_res.clay -= blueprint.obsidian.clay;
         let mut new_rob = rob;
         new_rob.obsidian += 1;
-        max = max.max(mine(blueprint, time, new_res, new_rob, best_so_far));
+        max = max.max(mine(blueprint, memo, time, new_res, new_rob, best_so_far));
     } 
     if new_clay {
         let mut new_res = res;
         new_res.ore -= blueprint.clay.ore;
         let mut new_rob = rob;
         new_rob.clay += 1;
-        max = max.max(mine(blueprint, time, new_res, new_rob, best_so_far));
+        max = max.max(mine(blueprint, memo, time, new_res, new_rob, best_so_far));
     } 
     if new_ore {
         let mut new_res = res;
         new_res.ore -= blueprint.ore.ore;
         let mut new_rob = rob;
         new_rob.ore += 1;
-        max = max.max(mine(blueprint, time, new_res, new_rob, best_so_far));
+        max = max.max(mine(blueprint, memo, time, new_res, new_rob, best_so_far));
     } 
-    max = max.max(mine(blueprint, time, res, rob, max));
+    max = max.max(mine(blueprint, memo, time, res, rob, max));
+    memo.insert(key, (max, best_so_far));
     max 
 }
 
-fn part1(input: &[Input]) -> Output {
+fn report(part: &str, memo: &MineMemo) {
+    let memo_stats = memo.stats();
+    stats::count(DAY, &format!("{part} states"), memo_stats.hits + memo_stats.misses);
+    stats::memo(DAY, part, &memo_stats);
+}
+
+pub fn part1(input: &[Input]) -> Output {
+    let _phase = stats::phase(DAY, "part1");
     input.par_iter()
         .map(|blueprint| {
             let res = Resources {
@@ -170,12 +196,16 @@ fn part1(input: &[Input]) -> Output {
                 obsidian: 0,
                 geode: 0,
             };
-            mine(blueprint, 24, res, robs, 0) * blueprint.id
+            let mut memo = MineMemo::bounded(MEMO_BOUND);
+            let geodes = mine(blueprint, &mut memo, 24, res, robs, 0);
+            report("part1", &memo);
+            geodes * blueprint.id
         })
         .sum()
 }
 
-fn part2(input: &[Input]) -> Output {
+pub fn part2(input: &[Input]) -> Output {
+    let _phase = stats::phase(DAY, "part2");
     input[0..3].par_iter().map(|blueprint| {
         let res = Resources {
             ore: 0,
@@ -189,7 +219,10 @@ fn part2(input: &[Input]) -> Output {
             obsidian: 0,
             geode: 0,
         };
-        mine(blueprint, 32, res, robs, 0)
+        let mut memo = MineMemo::bounded(MEMO_BOUND);
+        let geodes = mine(blueprint, &mut memo, 32, res, robs, 0);
+        report("part2", &memo);
+        geodes
     })
     .product()
 }
@@ -209,4 +242,21 @@ mod tests {
         let input = parse_input(get_input());
         assert_eq!(3080, part2(&input));
     }
+
+    fn example() -> Vec<String> {
+        [
+            "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.",
+            "Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+    }
+
+    // Part 2 takes the first three blueprints and the example only has two.
+    #[test]
+    fn day19_example() {
+        let input = parse_input(example());
+        assert_eq!(33, part1(&input));
+    }
 }
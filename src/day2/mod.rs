@@ -1,4 +1,6 @@
-use aoc_downloader::download_day;
+use crate::utils::download_day;
+
+pub mod strategy;
 
 const DAY: u32 = 2;
 
@@ -11,11 +13,51 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-fn parse_input(input: Vec<String>) -> Vec<Vec<char>> {
+// One line of the strategy guide: what the opponent plays and the column telling us how to respond.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Round {
+    pub opponent: char,
+    pub response: char,
+}
+
+pub type Input = Round;
+
+// Line numbers are counted from one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    Round { line: usize, text: String },
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Round { line, text } => write!(f, "Line {line}: expected two symbols, found {text:?}"),
+        }
+    }
+}
+
+// Every line has to be exactly two single character columns.
+pub fn parse_guide(input: &[String]) -> Result<Vec<Input>, ParseError> {
     input
         .iter()
-        .map(|v| v.chars().collect::<Vec<char>>())
-        .collect::<Vec<_>>()
+        .enumerate()
+        .map(|(idx, line)| {
+            let mut symbols = line.split_whitespace().map(|symbol| {
+                let mut chars = symbol.chars();
+                (chars.next(), chars.next())
+            });
+            match (symbols.next(), symbols.next(), symbols.next()) {
+                (Some((Some(opponent), None)), Some((Some(response), None)), None) => {
+                    Ok(Round { opponent, response })
+                }
+                _ => Err(ParseError::Round { line: idx + 1, text: line.clone() }),
+            }
+        })
+        .collect()
+}
+
+pub fn parse_input(input: Vec<String>) -> Vec<Input> {
+    parse_guide(&input).unwrap()
 }
 
 pub fn run_day() {
@@ -29,142 +71,220 @@ pub fn run_day() {
     );
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-enum Play {
-    Rock,
-    Paper,
-    Scissors,
+pub type Shape = usize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
 }
 
-impl From<char> for Play {
+impl From<char> for Outcome {
     fn from(input: char) -> Self {
         match input {
-            'A' | 'X' => Self::Rock,
-            'B' | 'Y' => Self::Paper,
-            'C' | 'Z' => Self::Scissors,
-            e => panic!("Unknown play: {e}"),
+            'X' => Self::Loss,
+            'Y' => Self::Draw,
+            'Z' => Self::Win,
+            e => panic!("Unkown result: {e}"),
         }
     }
 }
 
-impl PartialOrd for Play {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        use std::cmp::Ordering::*;
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
 
-        match (self, other) {
-            (Self::Rock, Self::Paper) => Some(Less),
-            (Self::Rock, Self::Scissors) => Some(Greater),
-            (Self::Paper, Self::Rock) => Some(Greater),
-            (Self::Paper, Self::Scissors) => Some(Less),
-            (Self::Scissors, Self::Rock) => Some(Less),
-            (Self::Scissors, Self::Paper) => Some(Greater),
-            (_, _) => Some(Equal),
+    pub fn index(&self) -> usize {
+        match self {
+            Self::Loss => 0,
+            Self::Draw => 1,
+            Self::Win => 2,
         }
     }
 }
 
-impl std::fmt::Display for Play {
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RulesError {
+    EvenCycle(usize),
+    TooFewShapes(usize),
+    Undecided(Shape, Shape),
+    Contradiction(Shape, Shape),
+    TooManyShapes(usize),
+    OutOfRange(Shape),
+    BeatsItself(Shape),
+    Unbeatable(Shape),
+    Harmless(Shape),
+}
+
+impl std::fmt::Display for RulesError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Self::Rock => "Rock",
-                Self::Paper => "Paper",
-                Self::Scissors => "Scissors",
-            }
-        )
+        match self {
+            Self::EvenCycle(n) => write!(f, "A cyclic game needs an odd number of shapes, got {n}"),
+            Self::TooFewShapes(n) => write!(f, "A game needs at least three shapes, got {n}"),
+            Self::Undecided(a, b) => write!(f, "No rule decides between shapes {a} and {b}"),
+            Self::Contradiction(a, b) => write!(f, "Shapes {a} and {b} beat each other"),
+            Self::TooManyShapes(n) => write!(f, "At most 13 shapes fit the guide alphabet, got {n}"),
+            Self::OutOfRange(shape) => write!(f, "There is no shape {shape}"),
+            Self::BeatsItself(shape) => write!(f, "Shape {shape} beats itself"),
+            Self::Unbeatable(shape) => write!(f, "No shape beats shape {shape}"),
+            Self::Harmless(shape) => write!(f, "Shape {shape} beats no shape"),
+        }
     }
 }
 
-impl Play {
-    pub fn scoring(&self) -> u32 {
-        match self {
-            Self::Rock => 1,
-            Self::Paper => 2,
-            Self::Scissors => 3,
+// The dominance relation, names and scoring of a rock-paper-scissors style game. Shapes are
+// numbered in the order of their names. Opponent plays are written with the first letters of the
+// alphabet and our plays with the last ones, so the classic game reads `A`-`C` and `X`-`Z`.
+#[derive(Clone, Debug)]
+pub struct Rules {
+    names: Vec<String>,
+    beats: Vec<Vec<bool>>,
+    pub shape_scores: Vec<u32>,
+    pub outcome_scores: [u32; 3],
+}
+
+impl Rules {
+    // `wins` lists every (winner, loser) pair, each pair of distinct shapes needs exactly one. Every
+    // shape has to win and lose against some other shape, so any outcome can be played for.
+    pub fn new(names: &[&str], wins: &[(Shape, Shape)]) -> Result<Self, RulesError> {
+        let n = names.len();
+        // With fewer shapes some shape always wins or loses every round.
+        if n < 3 {
+            return Err(RulesError::TooFewShapes(n));
         }
+        if n > 13 {
+            return Err(RulesError::TooManyShapes(n));
+        }
+        let mut beats = vec![vec![false; n]; n];
+        for &(winner, loser) in wins {
+            if let Some(&shape) = [winner, loser].iter().find(|&&shape| shape >= n) {
+                return Err(RulesError::OutOfRange(shape));
+            }
+            if winner == loser {
+                return Err(RulesError::BeatsItself(winner));
+            }
+            if beats[loser][winner] {
+                return Err(RulesError::Contradiction(winner, loser));
+            }
+            beats[winner][loser] = true;
+        }
+        if let Some((a, b)) = (0..n)
+            .flat_map(|a| ((a + 1)..n).map(move |b| (a, b)))
+            .find(|&(a, b)| !beats[a][b] && !beats[b][a])
+        {
+            return Err(RulesError::Undecided(a, b));
+        }
+        if let Some(shape) = (0..n).find(|&shape| !(0..n).any(|other| beats[other][shape])) {
+            return Err(RulesError::Unbeatable(shape));
+        }
+        if let Some(shape) = (0..n).find(|&shape| !beats[shape].contains(&true)) {
+            return Err(RulesError::Harmless(shape));
+        }
+        Ok(Rules {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            beats,
+            shape_scores: (1..=n as u32).collect(),
+            outcome_scores: [0, 3, 6],
+        })
     }
 
-    fn compare(&self, other: &Self) -> Outcome {
-        match (self, other) {
-            (Self::Rock, Self::Scissors) => Outcome::Win,
-            (Self::Rock, Self::Paper) => Outcome::Loss,
-            (Self::Paper, Self::Rock) => Outcome::Win,
-            (Self::Paper, Self::Scissors) => Outcome::Loss,
-            (Self::Scissors, Self::Paper) => Outcome::Win,
-            (Self::Scissors, Self::Rock) => Outcome::Loss,
-            (_, _) => Outcome::Draw,
+    // Every shape beats the shapes an odd number of steps behind it, which makes each shape win
+    // against exactly half of the others.
+    pub fn cyclic(names: &[&str]) -> Result<Self, RulesError> {
+        let n = names.len();
+        if n % 2 != 1 {
+            return Err(RulesError::EvenCycle(n));
         }
+        let wins = (0..n)
+            .flat_map(|a| (0..n).map(move |b| (a, b)))
+            .filter(|&(a, b)| ((a + n - b) % n) % 2 == 1)
+            .collect::<Vec<_>>();
+        Self::new(names, &wins)
     }
-}
 
-fn part1(input: &[Vec<char>]) -> u32 {
-    let strategy = input
-        .iter()
-        .map(|round| (Play::from(round[0]), Play::from(round[2])))
-        .collect::<Vec<_>>();
+    pub fn classic() -> Self {
+        Self::cyclic(&["Rock", "Paper", "Scissors"]).unwrap()
+    }
 
-    let mut score = 0;
-    for round in strategy {
-        score += round.1.compare(&round.0).scoring();
-        score += round.1.scoring();
+    pub fn lizard_spock() -> Self {
+        Self::cyclic(&["Rock", "Paper", "Scissors", "Spock", "Lizard"]).unwrap()
     }
-    score
-}
 
-pub enum Outcome {
-    Loss,
-    Draw,
-    Win,
-}
+    pub fn with_scores(mut self, shape_scores: Vec<u32>, outcome_scores: [u32; 3]) -> Self {
+        assert_eq!(self.names.len(), shape_scores.len(), "One score per shape");
+        self.shape_scores = shape_scores;
+        self.outcome_scores = outcome_scores;
+        self
+    }
 
-impl From<char> for Outcome {
-    fn from(input: char) -> Self {
-        match input {
-            'X' => Self::Loss,
-            'Y' => Self::Draw,
-            'Z' => Self::Win,
-            e => panic!("Unkown result: {e}"),
-        }
+    pub fn shapes(&self) -> usize {
+        self.names.len()
     }
-}
 
-impl Outcome {
-    pub fn scoring(&self) -> u32 {
-        match self {
-            Self::Loss => 0,
-            Self::Draw => 3,
-            Self::Win => 6,
-        }
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.names[shape]
+    }
+
+    pub fn opponent_shape(&self, symbol: char) -> Shape {
+        let shape = (symbol as u8).wrapping_sub(b'A') as Shape;
+        assert!(shape < self.shapes(), "Unknown play: {symbol}");
+        shape
+    }
+
+    pub fn player_shape(&self, symbol: char) -> Shape {
+        let first = b'Z' + 1 - self.shapes() as u8;
+        let shape = (symbol as u8).wrapping_sub(first) as Shape;
+        assert!(shape < self.shapes(), "Unknown play: {symbol}");
+        shape
     }
-}
 
-impl Play {
-    pub fn outcome(&self, result: Outcome) -> Self {
-        match (self, result) {
-            (Play::Rock, Outcome::Win) => Play::Paper,
-            (Play::Rock, Outcome::Loss) => Play::Scissors,
-            (Play::Paper, Outcome::Win) => Play::Scissors,
-            (Play::Paper, Outcome::Loss) => Play::Rock,
-            (Play::Scissors, Outcome::Win) => Play::Rock,
-            (Play::Scissors, Outcome::Loss) => Play::Paper,
-            (_, Outcome::Draw) => *self,
+    pub fn compare(&self, own: Shape, other: Shape) -> Outcome {
+        if self.beats[own][other] {
+            Outcome::Win
+        } else if self.beats[other][own] {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
         }
     }
+
+    // The first shape that gets us `outcome` against `other`, `Rules::new` makes sure there is one.
+    pub fn respond(&self, other: Shape, outcome: Outcome) -> Shape {
+        (0..self.shapes())
+            .find(|&own| self.compare(own, other) == outcome)
+            .unwrap()
+    }
+
+    pub fn score(&self, own: Shape, other: Shape) -> u32 {
+        self.shape_scores[own] + self.outcome_scores[self.compare(own, other).index()]
+    }
 }
 
-fn part2(input: &[Vec<char>]) -> u32 {
-    let playbook = input
+// Reads the response column as the shape to play.
+pub fn score_plays(rules: &Rules, guide: &[Round]) -> u32 {
+    guide
         .iter()
-        .map(|round| (Play::from(round[0]), Outcome::from(round[2])))
-        .collect::<Vec<_>>();
-    let mut score = 0;
-    for round in playbook {
-        score += round.1.scoring();
-        score += round.0.outcome(round.1).scoring();
-    }
-    score
+        .map(|round| rules.score(rules.player_shape(round.response), rules.opponent_shape(round.opponent)))
+        .sum()
+}
+
+// Reads the response column as the outcome the round has to end in.
+pub fn score_outcomes(rules: &Rules, guide: &[Round]) -> u32 {
+    guide
+        .iter()
+        .map(|round| {
+            let other = rules.opponent_shape(round.opponent);
+            rules.score(rules.respond(other, Outcome::from(round.response)), other)
+        })
+        .sum()
+}
+
+pub fn part1(input: &[Input]) -> u32 {
+    score_plays(&Rules::classic(), input)
+}
+
+pub fn part2(input: &[Input]) -> u32 {
+    score_outcomes(&Rules::classic(), input)
 }
 
 #[cfg(test)]
@@ -182,4 +302,51 @@ mod tests {
         let input = parse_input(get_input());
         assert_eq!(12316, part2(&input));
     }
+
+    #[test]
+    fn day2_example() {
+        let input = parse_input(vec!["A Y".into(), "B X".into(), "C Z".into()]);
+        assert_eq!(15, part1(&input));
+        assert_eq!(12, part2(&input));
+    }
+
+    #[test]
+    fn day2_lizard_spock() {
+        let rules = Rules::lizard_spock();
+        let shape = |name| (0..rules.shapes()).find(|&s| rules.name(s) == name).unwrap();
+        assert_eq!(Outcome::Win, rules.compare(shape("Spock"), shape("Scissors")));
+        assert_eq!(Outcome::Win, rules.compare(shape("Lizard"), shape("Spock")));
+        assert_eq!(Outcome::Win, rules.compare(shape("Paper"), shape("Spock")));
+        assert_eq!(Outcome::Loss, rules.compare(shape("Rock"), shape("Spock")));
+        assert_eq!(shape("Scissors"), rules.player_shape('X'));
+        assert_eq!(Err(RulesError::EvenCycle(4)), Rules::cyclic(&["a", "b", "c", "d"]).map(|_| ()));
+        assert_eq!(Err(RulesError::Undecided(0, 2)), Rules::new(&["a", "b", "c"], &[(1, 0), (2, 1)]).map(|_| ()));
+    }
+
+    #[test]
+    fn day2_invalid_rules() {
+        let rules = |wins: &[(Shape, Shape)]| Rules::new(&["a", "b", "c"], wins).map(|_| ());
+        assert_eq!(Err(RulesError::OutOfRange(5)), rules(&[(5, 0)]));
+        assert_eq!(Err(RulesError::OutOfRange(3)), rules(&[(0, 1), (1, 3)]));
+        assert_eq!(Err(RulesError::BeatsItself(0)), rules(&[(0, 0)]));
+        assert_eq!(Err(RulesError::Unbeatable(0)), rules(&[(0, 1), (0, 2), (1, 2)]));
+        let wins = [(1, 0), (2, 0), (3, 0), (1, 2), (2, 3), (3, 1)];
+        assert_eq!(Err(RulesError::Harmless(0)), Rules::new(&["a", "b", "c", "d"], &wins).map(|_| ()));
+        assert_eq!(Err(RulesError::TooFewShapes(0)), Rules::new(&[], &[]).map(|_| ()));
+        assert_eq!(Err(RulesError::TooFewShapes(1)), Rules::new(&["a"], &[]).map(|_| ()));
+        assert_eq!(Err(RulesError::TooFewShapes(2)), Rules::new(&["a", "b"], &[(0, 1)]).map(|_| ()));
+        assert_eq!(Err(RulesError::TooFewShapes(1)), Rules::cyclic(&["a"]).map(|_| ()));
+        assert_eq!(Ok(()), rules(&[(1, 0), (2, 1), (0, 2)]));
+    }
+
+    #[test]
+    fn day2_guide_errors() {
+        let guide = |lines: &[&str]| parse_guide(&lines.iter().map(|line| line.to_string()).collect::<Vec<_>>());
+        assert_eq!(Ok(vec![Round { opponent: 'A', response: 'Y' }]), guide(&["A Y"]));
+        let error = |line, text: &str| Err(ParseError::Round { line, text: text.to_string() });
+        assert_eq!(error(2, "B"), guide(&["A Y", "B"]));
+        assert_eq!(error(1, ""), guide(&["", "A Y"]));
+        assert_eq!(error(1, "AB X"), guide(&["AB X"]));
+        assert_eq!(error(3, "C Z X"), guide(&["A Y", "B X", "C Z X"]));
+    }
 }
@@ -0,0 +1,145 @@
+use std::fmt;
+
+use itertools::Itertools;
+
+use super::{Outcome, Round, Rules, Shape};
+
+// One way of reading the response column. The n-th entry belongs to the n-th response symbol,
+// `X`, `Y` and `Z` in the classic game.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Decoding {
+    Plays(Vec<Shape>),
+    Outcomes(Vec<Outcome>),
+}
+
+impl Decoding {
+    pub fn shape(&self, rules: &Rules, round: &Round, opponent: Shape) -> Shape {
+        let symbol = rules.player_shape(round.response);
+        match self {
+            Self::Plays(plays) => plays[symbol],
+            Self::Outcomes(outcomes) => rules.respond(opponent, outcomes[symbol]),
+        }
+    }
+}
+
+impl fmt::Display for Decoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entries = match self {
+            Self::Plays(plays) => plays.iter().map(|&p| format!("{p}")).collect::<Vec<_>>(),
+            Self::Outcomes(outcomes) => outcomes.iter().map(|o| format!("{o:?}")).collect::<Vec<_>>(),
+        };
+        write!(f, "{}", entries.join("/"))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Interpretation {
+    pub decoding: Decoding,
+    pub score: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub score: u32,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl Tally {
+    fn add(&mut self, rules: &Rules, own: Shape, other: Shape) {
+        self.score += rules.score(own, other);
+        match rules.compare(own, other) {
+            Outcome::Win => self.wins += 1,
+            Outcome::Draw => self.draws += 1,
+            Outcome::Loss => self.losses += 1,
+        }
+    }
+}
+
+// Every mapping of the response symbols onto shapes, followed by every mapping onto outcomes.
+// Outcome mappings only exist when there are as many symbols as outcomes.
+pub fn decodings(rules: &Rules) -> Vec<Decoding> {
+    let n = rules.shapes();
+    let mut decodings = (0..n)
+        .permutations(n)
+        .map(Decoding::Plays)
+        .collect::<Vec<_>>();
+    if n == Outcome::ALL.len() {
+        decodings.extend(
+            Outcome::ALL
+                .iter()
+                .copied()
+                .permutations(n)
+                .map(Decoding::Outcomes),
+        );
+    }
+    decodings
+}
+
+// Plays the guide against an opponent that sticks to its column.
+pub fn play(rules: &Rules, guide: &[Round], decoding: &Decoding) -> Tally {
+    let mut tally = Tally::default();
+    for round in guide {
+        let other = rules.opponent_shape(round.opponent);
+        tally.add(rules, decoding.shape(rules, round, other), other);
+    }
+    tally
+}
+
+pub fn interpretations(rules: &Rules, guide: &[Round]) -> Vec<Interpretation> {
+    decodings(rules)
+        .into_iter()
+        .map(|decoding| Interpretation {
+            score: play(rules, guide, &decoding).score,
+            decoding,
+        })
+        .collect()
+}
+
+pub fn best(interpretations: &[Interpretation]) -> Option<&Interpretation> {
+    interpretations.iter().max_by_key(|i| i.score)
+}
+
+pub fn worst(interpretations: &[Interpretation]) -> Option<&Interpretation> {
+    interpretations.iter().min_by_key(|i| i.score)
+}
+
+// Plays the guide against an opponent that follows its column only until it has seen us play,
+// afterwards it throws whatever beats our most frequent shape so far. Ties go to the lower shape.
+pub fn simulate_adaptive(rules: &Rules, guide: &[Round], decoding: &Decoding) -> Tally {
+    let mut tally = Tally::default();
+    let mut played = vec![0usize; rules.shapes()];
+    for round in guide {
+        let other = match played.iter().enumerate().max_by_key(|&(shape, &count)| (count, std::cmp::Reverse(shape))) {
+            Some((favourite, &count)) if count > 0 => rules.respond(favourite, Outcome::Win),
+            _ => rules.opponent_shape(round.opponent),
+        };
+        let own = decoding.shape(rules, round, other);
+        played[own] += 1;
+        tally.add(rules, own, other);
+    }
+    tally
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse_input;
+    use super::*;
+
+    #[test]
+    fn day2_strategy_search() {
+        let rules = Rules::classic();
+        let guide = parse_input(vec!["A Y".into(), "B X".into(), "C Z".into()]);
+        let interpretations = interpretations(&rules, &guide);
+        assert_eq!(12, interpretations.len());
+        assert_eq!(15, interpretations[0].score);
+        assert_eq!(Decoding::Outcomes(Outcome::ALL.to_vec()), interpretations[6].decoding);
+        assert_eq!(12, interpretations[6].score);
+        assert_eq!(24, best(&interpretations).unwrap().score);
+        assert_eq!(6, worst(&interpretations).unwrap().score);
+
+        let adaptive = simulate_adaptive(&rules, &guide, &Decoding::Plays(vec![0, 1, 2]));
+        assert_eq!(Tally { score: 24, wins: 3, draws: 0, losses: 0 }, adaptive);
+    }
+}
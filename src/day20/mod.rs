@@ -1,4 +1,4 @@
-use aoc_downloader::download_day;
+use crate::utils::download_day;
 use std::collections::VecDeque;
 
 const DAY: u32 = 20;
@@ -12,10 +12,10 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Input = i64;
-type Output = i64;
+pub type Input = i64;
+pub type Output = i64;
 
-fn parse_input(input: Vec<String>) -> Vec<Input> {
+pub fn parse_input(input: Vec<String>) -> Vec<Input> {
     input
         .iter()
         .map(|v| v.parse::<i64>().unwrap())
@@ -28,7 +28,7 @@ pub fn run_day() {
     println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
 }
 
-fn part1(input: &[Input]) -> Output {
+pub fn part1(input: &[Input]) -> Output {
     let length = input.len();
     let mut idxs = (0..length).collect::<VecDeque<_>>();
 
@@ -47,7 +47,7 @@ fn part1(input: &[Input]) -> Output {
     sum
 }
 
-fn part2(input: &[Input]) -> Output {
+pub fn part2(input: &[Input]) -> Output {
     let length = input.len();
     let input = input.iter().map(|&n| n * 811589153).collect::<Vec<_>>();
     let mut idxs = (0..length).collect::<VecDeque<_>>();
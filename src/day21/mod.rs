@@ -1,7 +1,10 @@
 use std::{str::FromStr, num::ParseIntError};
 use std::collections::HashMap;
 
-use aoc_downloader::download_day;
+use crate::memo::Memo;
+use crate::stats;
+
+use crate::utils::download_day;
 
 const DAY: u32 = 21;
 
@@ -14,11 +17,11 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Input = HashMap<String, Monkey>;
-type Output = f64;
+pub type Input = HashMap<String, Monkey>;
+pub type Output = f64;
 
 #[derive(Clone, Debug)]
-enum Monkey {
+pub enum Monkey {
     Job(String, String, char),
     Num(f64)
 }
@@ -47,7 +50,7 @@ impl FromStr for Monkey {
     }
 }
 
-fn parse_input(input: Vec<String>) -> Input {
+pub fn parse_input(input: Vec<String>) -> Input {
     input
         .iter()
         .map(|line| {
@@ -66,37 +69,65 @@ pub fn run_day() {
     println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
 }
 
-fn tree_walk(monkey_tree: &HashMap<String, Monkey>, node: &str) -> f64 {
+pub type YellMemo = Memo<String, f64>;
+
+pub fn tree_walk(monkey_tree: &HashMap<String, Monkey>, node: &str, memo: &mut YellMemo) -> f64 {
     if let Some(Monkey::Num(n)) = monkey_tree.get(node) {
         return *n;
     }
 
+    if let Some(result) = memo.get(node) {
+        return result;
+    }
+
     if let Some(Monkey::Job(left, right, op)) = monkey_tree.get(node) {
-        return match op {
-            '+' => tree_walk(monkey_tree, left) + tree_walk(monkey_tree, right),
-            '-' => tree_walk(monkey_tree, left) - tree_walk(monkey_tree, right),
-            '*' => tree_walk(monkey_tree, left) * tree_walk(monkey_tree, right),
-            '/' => tree_walk(monkey_tree, left) / tree_walk(monkey_tree, right),
+        let left = tree_walk(monkey_tree, left, memo);
+        let right = tree_walk(monkey_tree, right, memo);
+        let result = match op {
+            '+' => left + right,
+            '-' => left - right,
+            '*' => left * right,
+            '/' => left / right,
             '=' => {
-                if tree_walk(monkey_tree, left) == tree_walk(monkey_tree, right) {
+                if left == right {
                     0.0
                 } else {
                     1.0
                 }
             },
             _ => unreachable!("Unkown symbol {op}"),
-        }
+        };
+        memo.insert(node.to_string(), result);
+        return result;
     }
 
     unreachable!();
 }
 
-fn part1(input: &HashMap<String, Monkey>) -> Output {
+// All monkeys from `node` down to `target`, these are the only results that change with `target`.
+fn path_to(monkey_tree: &HashMap<String, Monkey>, node: &str, target: &str) -> Option<Vec<String>> {
+    if node == target {
+        return Some(vec![node.to_string()]);
+    }
+    if let Some(Monkey::Job(left, right, _)) = monkey_tree.get(node) {
+        for child in [left, right] {
+            if let Some(mut path) = path_to(monkey_tree, child, target) {
+                path.push(node.to_string());
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+pub fn part1(input: &HashMap<String, Monkey>) -> Output {
+    let _phase = stats::phase(DAY, "part1");
     let start = String::from("root");
-    tree_walk(input, &start)
+    tree_walk(input, &start, &mut YellMemo::new())
 }
 
-fn part2(input: &HashMap<String, Monkey>) -> f64{
+pub fn part2(input: &HashMap<String, Monkey>) -> f64{
+    let _phase = stats::phase(DAY, "part2");
     let mut input = input.clone();
     let (left, right) = &input.get(&"root".to_string()).and_then(|job| {
         if let Monkey::Job(m1, m2, _) = job {
@@ -106,7 +137,9 @@ fn part2(input: &HashMap<String, Monkey>) -> f64{
         }
     }).unwrap();
 
-    let result = tree_walk(&input, right);
+    let mut memo = YellMemo::new();
+    let result = tree_walk(&input, right, &mut memo);
+    let humn_path = path_to(&input, left, "humn").unwrap();
 
     let mut low = 0.0;
     let mut high = 10_000_000_000_000.0;
@@ -118,9 +151,16 @@ fn part2(input: &HashMap<String, Monkey>) -> f64{
                 *job = Monkey::Num(pivot);
             }
         });
-        match result.total_cmp(&tree_walk(&input, left)) {
+        for monkey in &humn_path {
+            memo.remove(monkey.as_str());
+        }
+        stats::count(DAY, "part2 bisection steps", 1);
+        match result.total_cmp(&tree_walk(&input, left, &mut memo)) {
             std::cmp::Ordering::Less => low = pivot,
-            std::cmp::Ordering::Equal => return pivot,
+            std::cmp::Ordering::Equal => {
+                stats::memo(DAY, "part2", &memo.stats());
+                return pivot;
+            },
             std::cmp::Ordering::Greater => high = pivot,
         }
     }
@@ -142,4 +182,35 @@ mod tests {
         let input = parse_input(get_input());
         assert_eq!(3876907167495.0, part2(&input));
     }
+
+    fn example() -> Vec<String> {
+        [
+            "root: pppw + sjmn",
+            "dbpl: 5",
+            "cczh: sllz + lgvd",
+            "zczc: 2",
+            "ptdq: humn - dvpt",
+            "dvpt: 3",
+            "lfqf: 4",
+            "humn: 5",
+            "ljgn: 2",
+            "sjmn: drzm * dbpl",
+            "sllz: 4",
+            "pppw: cczh / lfqf",
+            "lgvd: ljgn * ptdq",
+            "drzm: hmdt - zczc",
+            "hmdt: 32",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+    }
+
+    // The bisection in part 2 assumes the root's left side shrinks as `humn` grows. In the example
+    // it grows instead, so only part 1 is checked.
+    #[test]
+    fn day21_example() {
+        let input = parse_input(example());
+        assert_eq!(152.0, part1(&input));
+    }
 }
@@ -1,4 +1,4 @@
-use aoc_downloader::download_day;
+use crate::utils::download_day;
 use std::collections::{HashMap, HashSet};
 
 const DAY: u32 = 22;
@@ -12,12 +12,12 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Output = i64;
+pub type Output = i64;
 
 type Coords = (i64, i64);
 
 #[derive(Debug)]
-enum Command {
+pub enum Command {
     Direction(char),
     Movement(i64),
 }
@@ -41,7 +41,7 @@ fn from_str(line: &str) -> Vec<Command> {
     comms
 }
 
-fn parse_input(input: Vec<String>) -> (HashMap<Coords, char>, Vec<Command>) {
+pub fn parse_input(input: Vec<String>) -> (HashMap<Coords, char>, Vec<Command>) {
     let mut map = HashMap::new();
     let mut commands = vec![];
     let mut path = false;
@@ -193,7 +193,7 @@ fn walk_map(start: Coords, map: &HashMap<Coords, char>, comms: &Vec<Command>) ->
     me
 }
 
-fn part1(input: &(HashMap<Coords, char>, Vec<Command>)) -> Output {
+pub fn part1(input: &(HashMap<Coords, char>, Vec<Command>)) -> Output {
     let map = input.0.clone();
     let comms = &input.1;
     let upper_left_x = map
@@ -427,7 +427,7 @@ fn walk_cube(start: Coords, map: &HashMap<Coords, char>, comms: &Vec<Command>) -
 
     me
 }
-fn part2(input: &(HashMap<Coords, char>, Vec<Command>)) -> Output {
+pub fn part2(input: &(HashMap<Coords, char>, Vec<Command>)) -> Output {
     let map = input.0.clone();
     let comms = &input.1;
     let upper_left_x = map
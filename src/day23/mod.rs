@@ -1,7 +1,9 @@
-use aoc_downloader::download_day;
+use crate::utils::download_day;
 use std::collections::{HashSet, HashMap, VecDeque};
 use once_cell::unsync::Lazy;
 
+use crate::stats;
+
 const DAY: u32 = 23;
 
 fn get_input() -> Vec<String> {
@@ -14,10 +16,10 @@ fn get_input() -> Vec<String> {
 }
 
 type Coords = (i64, i64);
-type Input = HashSet<Coords>;
-type Output = i64;
+pub type Input = HashSet<Coords>;
+pub type Output = i64;
 
-fn parse_input(input: Vec<String>) -> Input {
+pub fn parse_input(input: Vec<String>) -> Input {
     let mut map = HashSet::new();
     for (y, row) in input.iter().enumerate() {
         for (x, spot) in row.chars().enumerate() {
@@ -94,12 +96,14 @@ fn get_movement_options(round: usize) -> Vec<Directions>{
     Vec::from(options)
 }
 
-fn part1(input: &Input) -> Output {
+pub fn part1(input: &Input) -> Output {
     use Directions::*;
+    let _phase = stats::phase(DAY, "part1");
 
     let mut map = input.clone();
 
     let mut i = 1;
+    let mut moves = 0;
     loop {
         let mut proposed_movement: HashMap<Coords, (i64, Coords)> = HashMap::new();
         for elf in &map {
@@ -166,6 +170,7 @@ fn part1(input: &Input) -> Output {
             }
             assert!(map.remove(&movement.1.1));
             map.insert(movement.0);
+            moves += 1;
         }
         assert!(map.len() == input.len());
         
@@ -174,6 +179,8 @@ fn part1(input: &Input) -> Output {
             break;
         }
     }
+    stats::count(DAY, "part1 rounds", i as u64 - 1);
+    stats::count(DAY, "part1 moves", moves);
 
     let min_x = map.iter()
         .map(|(x, _)| x)
@@ -198,12 +205,14 @@ fn part1(input: &Input) -> Output {
     (max_x - min_x + 1) * (max_y - min_y + 1) - input.len() as i64
 }
 
-fn part2(input: &Input) -> Output {
+pub fn part2(input: &Input) -> Output {
     use Directions::*;
+    let _phase = stats::phase(DAY, "part2");
 
     let mut map = input.clone();
 
     let mut i = 1;
+    let mut moves = 0;
     loop {
         let mut proposed_movement: HashMap<Coords, (i64, Coords)> = HashMap::new();
         for elf in &map {
@@ -274,11 +283,14 @@ fn part2(input: &Input) -> Output {
             }
             assert!(map.remove(&movement.1.1));
             map.insert(movement.0);
+            moves += 1;
         }
         assert!(map.len() == input.len());
         
         i += 1;
     }
+    stats::count(DAY, "part2 rounds", i as u64);
+    stats::count(DAY, "part2 moves", moves);
     i as i64
 }
 
@@ -1,7 +1,9 @@
 use std::collections::HashSet;
 use once_cell::unsync::Lazy;
 
-use aoc_downloader::download_day;
+use crate::stats;
+
+use crate::utils::download_day;
 
 const DAY: u32 = 24;
 
@@ -14,10 +16,10 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Output = u64;
-type Coords = (i64, i64);
+pub type Output = u64;
+pub type Coords = (i64, i64);
 
-fn parse_input(input: Vec<String>) -> (Vec<(Blizzard, Coords)>, i64, i64) {
+pub fn parse_input(input: Vec<String>) -> (Vec<(Blizzard, Coords)>, i64, i64) {
     use Blizzard::*;
 
     let mut map = Vec::new();
@@ -41,7 +43,7 @@ fn parse_input(input: Vec<String>) -> (Vec<(Blizzard, Coords)>, i64, i64) {
 }
 
 #[derive(Clone, Copy, Debug, Hash)]
-enum Blizzard {
+pub enum Blizzard {
     Right,
     Left,
     Up,
@@ -97,7 +99,13 @@ fn own_moves(pos: Coords, max: Coords) -> Vec<Coords> {
     options
 }
 
-fn part1(input: &(Vec<(Blizzard, Coords)>, i64, i64)) -> Output {
+fn report(part: &str, expanded: u64, widest: u64) {
+    stats::count(DAY, &format!("{part} states"), expanded);
+    stats::maximum(DAY, &format!("{part} frontier"), widest);
+}
+
+pub fn part1(input: &(Vec<(Blizzard, Coords)>, i64, i64)) -> Output {
+    let _phase = stats::phase(DAY, "part1");
     let start = (1, 0);
 
     let mut map = input.0.clone();
@@ -106,6 +114,8 @@ fn part1(input: &(Vec<(Blizzard, Coords)>, i64, i64)) -> Output {
     let end = (max_x - 1, max_y);
 
     let mut minutes = 0;
+    let mut expanded = 0;
+    let mut widest = 0;
     let mut prev = HashSet::new();
     prev.insert(start);
     loop {
@@ -125,15 +135,19 @@ fn part1(input: &(Vec<(Blizzard, Coords)>, i64, i64)) -> Output {
             }
         }
         minutes += 1;
+        expanded += prev.len() as u64;
+        widest = widest.max(next_options.len() as u64);
         if next_options.contains(&end) {
             break;
         }
         prev = next_options.clone();
     }
+    report("part1", expanded, widest);
     minutes
 }
 
-fn part2(input: &(Vec<(Blizzard, Coords)>, i64, i64)) -> Output {
+pub fn part2(input: &(Vec<(Blizzard, Coords)>, i64, i64)) -> Output {
+    let _phase = stats::phase(DAY, "part2");
     let start = (1, 0);
 
     let mut map = input.0.clone();
@@ -142,6 +156,8 @@ fn part2(input: &(Vec<(Blizzard, Coords)>, i64, i64)) -> Output {
     let end = (max_x - 1, max_y);
 
     let mut minutes = 0;
+    let mut expanded = 0;
+    let mut widest = 0;
     let mut prev = HashSet::new();
     prev.insert(start);
     let mut run = 1;
@@ -162,6 +178,8 @@ fn part2(input: &(Vec<(Blizzard, Coords)>, i64, i64)) -> Output {
             }
         }
         minutes += 1;
+        expanded += prev.len() as u64;
+        widest = widest.max(next_options.len() as u64);
         if run == 1 && next_options.contains(&end) {
                 run = 2;
                 next_options.clear();
@@ -177,6 +195,7 @@ fn part2(input: &(Vec<(Blizzard, Coords)>, i64, i64)) -> Output {
         }
         prev = next_options.clone();
     }
+    report("part2", expanded, widest);
     minutes
 }
 
@@ -1,4 +1,4 @@
-use aoc_downloader::download_day;
+use crate::utils::download_day;
 
 const DAY: u32 = 25;
 
@@ -11,10 +11,10 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Input = String;
-type Output = String;
+pub type Input = String;
+pub type Output = String;
 
-fn snafu_to_decimal(snafu: &str) -> i64 {
+pub fn snafu_to_decimal(snafu: &str) -> i64 {
     let mut num = 0;
     for (exp, c) in snafu.chars().rev().enumerate() {
         let digit = match c {
@@ -30,7 +30,7 @@ fn snafu_to_decimal(snafu: &str) -> i64 {
     num
 }
 
-fn decimal_to_snafu(mut decimal: i64) -> String {
+pub fn decimal_to_snafu(mut decimal: i64) -> String {
     let mut snafu = String::new();
     loop {
         let base = decimal % 5;
@@ -68,7 +68,7 @@ fn decimal_to_snafu(mut decimal: i64) -> String {
     snafu.chars().rev().collect::<String>()
 }
 
-fn parse_input(input: Vec<String>) -> Vec<Input> {
+pub fn parse_input(input: Vec<String>) -> Vec<Input> {
     input
         .iter()
         .map(String::from)
@@ -81,14 +81,14 @@ pub fn run_day() {
     println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
 }
 
-fn part1(input: &[Input]) -> Output {
+pub fn part1(input: &[Input]) -> Output {
     let sum = input.iter()
         .map(|n| snafu_to_decimal(n))
         .sum();
     decimal_to_snafu(sum)
 }
 
-fn part2(_input: &[Input]) -> Output {
+pub fn part2(_input: &[Input]) -> Output {
     String::from("Done")
 }
 
@@ -1,6 +1,4 @@
-use aoc_downloader::download_day;
-use std::collections::HashSet;
-use std::iter::FromIterator;
+use crate::utils::download_day;
 
 const DAY: u32 = 3;
 
@@ -13,11 +11,33 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-fn parse_input(input: Vec<String>) -> Vec<Vec<char>> {
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    Item { line: usize, item: char },
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Item { line, item } => write!(f, "Line {line}: {item:?} is not an item"),
+        }
+    }
+}
+
+// Every item has to be a letter, lines are counted from one.
+pub fn parse_backpacks(input: &[String]) -> Result<Vec<Vec<char>>, ParseError> {
     input
         .iter()
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect::<Vec<_>>()
+        .enumerate()
+        .map(|(idx, line)| match line.chars().find(|item| !item.is_ascii_alphabetic()) {
+            Some(item) => Err(ParseError::Item { line: idx + 1, item }),
+            None => Ok(line.chars().collect::<Vec<char>>()),
+        })
+        .collect()
+}
+
+pub fn parse_input(input: Vec<String>) -> Vec<Vec<char>> {
+    parse_backpacks(&input).unwrap()
 }
 
 pub fn run_day() {
@@ -31,43 +51,181 @@ pub fn run_day() {
     );
 }
 
-fn score_item(item: char) -> u32 {
-    if item.is_lowercase() {
-        item as u32 - ('a' as u32) + 1
-    } else {
-        item as u32 - ('A' as u32) + 27
+pub fn score_item(item: char) -> u32 {
+    match item {
+        'a'..='z' => item as u32 - ('a' as u32) + 1,
+        'A'..='Z' => item as u32 - ('A' as u32) + 27,
+        e => panic!("{e:?} is not an item"),
+    }
+}
+
+pub fn item_for_priority(priority: u32) -> char {
+    match priority {
+        1..=26 => (b'a' + (priority - 1) as u8) as char,
+        27..=52 => (b'A' + (priority - 27) as u8) as char,
+        e => panic!("No item has priority {e}"),
+    }
+}
+
+// Anything but the 52 letters an item can be.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidItem(pub char);
+
+impl std::fmt::Display for InvalidItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?} is not an item", self.0)
+    }
+}
+
+// Bit n is set if the item with priority n is present, so the sum over the set bits is the
+// priority of the whole set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ItemSet(pub u64);
+
+impl ItemSet {
+    pub const EMPTY: ItemSet = ItemSet(0);
+    pub const ALL: ItemSet = ItemSet(((1 << 52) - 1) << 1);
+
+    // Leaves the set alone if `item` isn't one.
+    pub fn insert(&mut self, item: char) -> Result<(), InvalidItem> {
+        if !item.is_ascii_alphabetic() {
+            return Err(InvalidItem(item));
+        }
+        self.0 |= 1 << score_item(item);
+        Ok(())
+    }
+
+    pub fn try_from_items(items: impl IntoIterator<Item = char>) -> Result<ItemSet, InvalidItem> {
+        let mut set = ItemSet::EMPTY;
+        items.into_iter().try_for_each(|item| set.insert(item))?;
+        Ok(set)
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        item.is_ascii_alphabetic() && self.0 & (1 << score_item(item)) != 0
+    }
+
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn union(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn priorities(&self) -> impl Iterator<Item = u32> + '_ {
+        (1..=52).filter(|&priority| self.0 & (1 << priority) != 0)
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = char> + '_ {
+        self.priorities().map(item_for_priority)
+    }
+
+    pub fn priority(&self) -> u32 {
+        self.priorities().sum()
+    }
+}
+
+// Panics on anything that isn't an item, like `score_item`. Use `try_from_items` for input that
+// hasn't been through `parse_backpacks`.
+impl FromIterator<char> for ItemSet {
+    fn from_iter<I: IntoIterator<Item = char>>(items: I) -> Self {
+        ItemSet::try_from_items(items).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl<'a> FromIterator<&'a char> for ItemSet {
+    fn from_iter<I: IntoIterator<Item = &'a char>>(items: I) -> Self {
+        items.into_iter().copied().collect()
+    }
+}
+
+// Items present in every one of the sets, an empty input shares nothing.
+pub fn shared(sets: impl IntoIterator<Item = ItemSet>) -> ItemSet {
+    let mut sets = sets.into_iter();
+    match sets.next() {
+        Some(first) => sets.fold(first, ItemSet::intersection),
+        None => ItemSet::EMPTY,
     }
 }
 
-fn part1(input: &Vec<Vec<char>>) -> u32 {
-    let mut priority = 0;
-    for backpack in input {
-        let bagsize = backpack.len();
-        let left_compartment: HashSet<&char> = HashSet::from_iter(&backpack[..bagsize / 2]);
-        let right_compartment = HashSet::from_iter(&backpack[bagsize / 2..]);
-        let in_common = left_compartment.intersection(&right_compartment);
-        priority += in_common
-            .into_iter()
-            .map(|&&item| score_item(item))
-            .sum::<u32>();
+pub fn compartments(backpack: &[char]) -> (ItemSet, ItemSet) {
+    let (left, right) = backpack.split_at(backpack.len() / 2);
+    (left.iter().collect(), right.iter().collect())
+}
+
+// The items shared by each group of `group_size` consecutive elves. A trailing group may be smaller.
+pub fn group_items(input: &[Vec<char>], group_size: usize) -> Vec<ItemSet> {
+    assert!(group_size > 0, "Groups need at least one elf");
+    input
+        .chunks(group_size)
+        .map(|group| shared(group.iter().map(|backpack| backpack.iter().collect())))
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BadgeError {
+    EmptyGroup,
+    IncompleteGroup { group: usize, size: usize },
+    NoBadge { group: usize },
+    Ambiguous { group: usize, items: Vec<char> },
+}
+
+impl std::fmt::Display for BadgeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::EmptyGroup => write!(f, "Groups need at least one elf"),
+            Self::IncompleteGroup { group, size } => write!(f, "Group {group} only has {size} elves"),
+            Self::NoBadge { group } => write!(f, "Group {group} shares no item"),
+            Self::Ambiguous { group, items } => {
+                write!(f, "Group {group} shares {}", items.iter().collect::<String>())
+            }
+        }
     }
-    priority
 }
 
-fn part2(input: &[Vec<char>]) -> u32 {
-    let mut priority = 0;
-    for backpack in input.chunks(3) {
-        let elf1: HashSet<&char> = HashSet::from_iter(&backpack[0]);
-        let elf2 = HashSet::from_iter(&backpack[1]);
-        let elf3 = HashSet::from_iter(&backpack[2]);
-        let in_common = elf1.intersection(&elf2).cloned().collect::<HashSet<_>>();
-        let in_common = in_common.intersection(&elf3);
-        priority += in_common
-            .into_iter()
-            .map(|&&item| score_item(item))
-            .sum::<u32>();
+// The badge of every group, checking that each group is complete and shares exactly one item.
+pub fn badges(input: &[Vec<char>], group_size: usize) -> Result<Vec<char>, BadgeError> {
+    if group_size == 0 {
+        return Err(BadgeError::EmptyGroup);
     }
-    priority
+    input
+        .chunks(group_size)
+        .zip(group_items(input, group_size))
+        .enumerate()
+        .map(|(group, (elves, items))| {
+            if elves.len() != group_size {
+                return Err(BadgeError::IncompleteGroup { group, size: elves.len() });
+            }
+            match items.len() {
+                0 => Err(BadgeError::NoBadge { group }),
+                1 => Ok(items.items().next().unwrap()),
+                _ => Err(BadgeError::Ambiguous { group, items: items.items().collect() }),
+            }
+        })
+        .collect()
+}
+
+pub fn part1(input: &[Vec<char>]) -> u32 {
+    input
+        .iter()
+        .map(|backpack| {
+            let (left, right) = compartments(backpack);
+            left.intersection(right).priority()
+        })
+        .sum()
+}
+
+pub fn part2(input: &[Vec<char>]) -> u32 {
+    group_items(input, 3).iter().map(ItemSet::priority).sum()
 }
 
 #[cfg(test)]
@@ -85,4 +243,59 @@ mod tests {
         let input = parse_input(get_input());
         assert_eq!(2752, part2(&input));
     }
+
+    fn example() -> Vec<Vec<char>> {
+        parse_input(
+            [
+                "vJrwpWtwJgWrhcsFMMfFFhFp",
+                "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+                "PmmdzqPrVvPwwTWBwg",
+                "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+                "ttgJtRGJQctTZtZT",
+                "CrZsJsPPZsGzwwsLwLmpwMDw",
+            ]
+            .iter()
+            .map(|line| line.to_string())
+            .collect(),
+        )
+    }
+
+    #[test]
+    fn day3_example() {
+        let input = example();
+        assert_eq!(157, part1(&input));
+        assert_eq!(70, part2(&input));
+        assert_eq!(Ok(vec!['r', 'Z']), badges(&input, 3));
+    }
+
+    #[test]
+    fn day3_badge_validation() {
+        let input = example();
+        assert_eq!(Err(BadgeError::IncompleteGroup { group: 1, size: 2 }), badges(&input[..5], 3));
+        assert_eq!(Err(BadgeError::NoBadge { group: 0 }), badges(&input, 6));
+        assert_eq!(
+            Err(BadgeError::Ambiguous { group: 0, items: vec!['f', 'r', 's', 'F', 'M'] }),
+            badges(&input, 2)
+        );
+        let set = "aZz".chars().collect::<ItemSet>();
+        assert_eq!(vec!['a', 'z', 'Z'], set.items().collect::<Vec<_>>());
+        assert_eq!(1 + 26 + 52, set.priority());
+        assert_eq!(Err(BadgeError::EmptyGroup), badges(&input, 0));
+    }
+
+    #[test]
+    fn day3_invalid_items() {
+        assert_eq!(Err(InvalidItem('1')), ItemSet::try_from_items("ab1 \u{e9}".chars()));
+        assert_eq!(Err(InvalidItem('\u{e9}')), ItemSet::try_from_items("ab\u{e9}".chars()));
+        let mut set = ItemSet::try_from_items("ab".chars()).unwrap();
+        assert_eq!(Err(InvalidItem(' ')), set.insert(' '));
+        assert_eq!(Ok(()), set.insert('Z'));
+        assert_eq!(vec!['a', 'b', 'Z'], set.items().collect::<Vec<_>>());
+        assert!(!set.contains('1') && set.contains('b'));
+        assert!(std::panic::catch_unwind(|| "a1".chars().collect::<ItemSet>()).is_err());
+
+        let input = ["abcd", "ab1d"].map(String::from);
+        assert_eq!(Err(ParseError::Item { line: 2, item: '1' }), parse_backpacks(&input));
+        assert_eq!(Ok(vec![vec!['a', 'b', 'c', 'd']]), parse_backpacks(&input[..1]));
+    }
 }
@@ -0,0 +1,202 @@
+use super::{ElfPair, Section};
+
+// A maximal run of sections that is assigned to the same number of elves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Segment {
+    pub section: Section,
+    pub elves: usize,
+}
+
+// How often every section in `range` is assigned, computed with a sweep over the assignment
+// boundaries so the cost depends on the number of elves and not on the length of the range.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Coverage {
+    pub range: Section,
+    pub segments: Vec<Segment>,
+}
+
+// A section running backwards, elf `None` is the range itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoverageError {
+    Reversed { elf: Option<usize>, section: Section },
+}
+
+impl std::fmt::Display for CoverageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Reversed { elf: Some(elf), section } => {
+                write!(f, "Elf {elf} is assigned {}-{}, which runs backwards", section.start, section.end)
+            }
+            Self::Reversed { elf: None, section } => {
+                write!(f, "Range {}-{} runs backwards", section.start, section.end)
+            }
+        }
+    }
+}
+
+// Both the sweep and the greedy cover assume every section has `start <= end`.
+fn check(sections: &[Section], range: Section) -> Result<(), CoverageError> {
+    if range.start > range.end {
+        return Err(CoverageError::Reversed { elf: None, section: range });
+    }
+    match sections.iter().position(|section| section.start > section.end) {
+        Some(elf) => Err(CoverageError::Reversed { elf: Some(elf), section: sections[elf] }),
+        None => Ok(()),
+    }
+}
+
+// Splits every pair into its two assignments, elf `2 * n` and `2 * n + 1` belong to pair `n`.
+pub fn assignments(pairs: &[ElfPair]) -> Vec<Section> {
+    pairs.iter().flat_map(|pair| [pair.first, pair.second]).collect()
+}
+
+// The smallest range holding every assignment.
+pub fn span(sections: &[Section]) -> Option<Section> {
+    Some(Section {
+        start: sections.iter().map(|s| s.start).min()?,
+        end: sections.iter().map(|s| s.end).max()?,
+    })
+}
+
+impl Coverage {
+    pub fn new(sections: &[Section], range: Section) -> Result<Self, CoverageError> {
+        check(sections, range)?;
+        let mut events = sections
+            .iter()
+            .flat_map(|s| [(s.start as u64, 1i64), (s.end as u64 + 1, -1)])
+            .collect::<Vec<_>>();
+        events.push((range.start as u64, 0));
+        events.sort_unstable();
+
+        let mut segments: Vec<Segment> = vec![];
+        let mut elves = 0i64;
+        let mut i = 0;
+        while i < events.len() {
+            let position = events[i].0;
+            while i < events.len() && events[i].0 == position {
+                elves += events[i].1;
+                i += 1;
+            }
+            let next = events.get(i).map_or(u64::MAX, |e| e.0);
+            let start = position.max(range.start as u64);
+            let end = (next - 1).min(range.end as u64);
+            if start > end {
+                continue;
+            }
+            let section = Section { start: start as u32, end: end as u32 };
+            match segments.last_mut() {
+                Some(last) if last.elves == elves as usize => last.section.end = section.end,
+                _ => segments.push(Segment { section, elves: elves as usize }),
+            }
+        }
+        Ok(Coverage { range, segments })
+    }
+
+    pub fn uncovered(&self) -> Vec<Section> {
+        self.segments
+            .iter()
+            .filter(|segment| segment.elves == 0)
+            .map(|segment| segment.section)
+            .collect()
+    }
+
+    // Sections worked on by more than one elf, with the number of elves on them.
+    pub fn overlapping(&self) -> Vec<Segment> {
+        self.segments
+            .iter()
+            .filter(|segment| segment.elves > 1)
+            .copied()
+            .collect()
+    }
+
+    pub fn max_elves(&self) -> usize {
+        self.segments.iter().map(|segment| segment.elves).max().unwrap_or(0)
+    }
+}
+
+// Fewest elves whose assignments cover all of `range`, by index into `sections`. Greedily takes
+// the assignment reaching furthest among those starting inside the covered prefix.
+pub fn minimal_cover(sections: &[Section], range: Section) -> Result<Option<Vec<usize>>, CoverageError> {
+    check(sections, range)?;
+    let mut order = (0..sections.len()).collect::<Vec<_>>();
+    order.sort_unstable_by_key(|&elf| sections[elf].start);
+
+    let mut cover = vec![];
+    let mut next = range.start as u64;
+    let mut candidates = order.into_iter().peekable();
+    let mut furthest: Option<usize> = None;
+    while next <= range.end as u64 {
+        while let Some(&elf) = candidates.peek() {
+            if sections[elf].start as u64 > next {
+                break;
+            }
+            match furthest {
+                Some(best) if sections[best].end >= sections[elf].end => (),
+                _ => furthest = Some(elf),
+            }
+            candidates.next();
+        }
+        let Some(elf) = furthest.filter(|&elf| sections[elf].end as u64 >= next) else {
+            return Ok(None);
+        };
+        cover.push(elf);
+        next = sections[elf].end as u64 + 1;
+    }
+    Ok(Some(cover))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse_input;
+    use super::*;
+
+    #[test]
+    fn day4_coverage() {
+        let input = ["2-4,6-8", "2-3,4-5", "5-7,7-9", "2-8,3-7", "6-6,4-6", "2-6,4-8"];
+        let sections = assignments(&parse_input(input.iter().map(|l| l.to_string()).collect()));
+        let range = span(&sections).unwrap();
+        let coverage = Coverage::new(&sections, range).unwrap();
+        assert_eq!(Section { start: 2, end: 9 }, range);
+        assert!(coverage.uncovered().is_empty());
+        assert_eq!(
+            Segment { section: Section { start: 9, end: 9 }, elves: 1 },
+            *coverage.segments.last().unwrap()
+        );
+        assert_eq!(8, coverage.max_elves());
+        assert_eq!(Ok(Some(vec![6, 5])), minimal_cover(&sections, range));
+    }
+
+    #[test]
+    fn day4_coverage_gaps() {
+        let sections = [Section { start: 1, end: 2 }, Section { start: 5, end: 6 }, Section { start: 2, end: 2 }];
+        let range = Section { start: 0, end: 7 };
+        let coverage = Coverage::new(&sections, range).unwrap();
+        assert_eq!(
+            vec![
+                Section { start: 0, end: 0 },
+                Section { start: 3, end: 4 },
+                Section { start: 7, end: 7 }
+            ],
+            coverage.uncovered()
+        );
+        assert_eq!(
+            vec![Segment { section: Section { start: 2, end: 2 }, elves: 2 }],
+            coverage.overlapping()
+        );
+        assert_eq!(Ok(None), minimal_cover(&sections, Section { start: 1, end: 6 }));
+        assert_eq!(Ok(Some(vec![0])), minimal_cover(&sections, Section { start: 1, end: 2 }));
+    }
+
+    #[test]
+    fn day4_reversed_sections() {
+        let reversed = Section { start: 5, end: 3 };
+        let sections = [Section { start: 1, end: 4 }, reversed, Section { start: 2, end: 9 }];
+        let range = Section { start: 1, end: 9 };
+        let error = CoverageError::Reversed { elf: Some(1), section: reversed };
+        assert_eq!(Err(error), Coverage::new(&sections, range));
+        assert_eq!(Err(error), minimal_cover(&sections, range));
+        let error = CoverageError::Reversed { elf: None, section: reversed };
+        assert_eq!(Err(error), Coverage::new(&sections[..1], reversed));
+        assert_eq!(Err(error), minimal_cover(&sections[..1], reversed));
+    }
+}
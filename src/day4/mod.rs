@@ -1,6 +1,8 @@
 use std::{str::FromStr, string::ParseError};
 
-use aoc_downloader::download_day;
+use crate::utils::download_day;
+
+pub mod coverage;
 
 const DAY: u32 = 4;
 
@@ -13,9 +15,10 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-struct Section {
-    start: u32,
-    end: u32,
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Section {
+    pub start: u32,
+    pub end: u32,
 }
 
 impl Section {
@@ -30,9 +33,9 @@ impl Section {
     }
 }
 
-struct ElfPair {
-    first: Section,
-    second: Section,
+pub struct ElfPair {
+    pub first: Section,
+    pub second: Section,
 }
 
 impl ElfPair {
@@ -69,7 +72,7 @@ impl FromStr for ElfPair {
     }
 }
 
-fn parse_input(input: Vec<String>) -> Vec<ElfPair> {
+pub fn parse_input(input: Vec<String>) -> Vec<ElfPair> {
     input
         .iter()
         .map(|elfpair| ElfPair::from_str(elfpair).unwrap())
@@ -87,11 +90,11 @@ pub fn run_day() {
     );
 }
 
-fn part1(input: &[ElfPair]) -> u32 {
+pub fn part1(input: &[ElfPair]) -> u32 {
     input.iter().filter(|ep| ep.one_bored()).count() as u32
 }
 
-fn part2(input: &Vec<ElfPair>) -> u32 {
+pub fn part2(input: &[ElfPair]) -> u32 {
     let all_pairs = input.len() as u32;
     all_pairs - input.iter().filter(|ep| ep.full_work()).count() as u32
 }
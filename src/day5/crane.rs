@@ -0,0 +1,204 @@
+use super::{Command, Stacks};
+
+// A crane moves the crates of a command in one or more lifts. Every lift takes crates off the top
+// of the source stack and puts them down on the target in the same order.
+pub trait Crane {
+    fn name(&self) -> String;
+
+    // How many crates the next lift takes while `remaining` crates of the command still have to move.
+    fn lift_size(&self, remaining: usize) -> usize;
+}
+
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        String::from("CrateMover 9000")
+    }
+
+    fn lift_size(&self, _remaining: usize) -> usize {
+        1
+    }
+}
+
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        String::from("CrateMover 9001")
+    }
+
+    fn lift_size(&self, remaining: usize) -> usize {
+        remaining
+    }
+}
+
+pub struct LimitedCrane {
+    pub capacity: usize,
+}
+
+impl Crane for LimitedCrane {
+    fn name(&self) -> String {
+        format!("Crane lifting {} crates", self.capacity)
+    }
+
+    fn lift_size(&self, remaining: usize) -> usize {
+        remaining.min(self.capacity)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lift {
+    pub command: usize,
+    pub source: usize,
+    pub target: usize,
+    pub crates: Vec<char>,
+}
+
+impl Lift {
+    fn apply(&self, stacks: &mut Stacks) {
+        let source = &mut stacks.stacks[self.source];
+        source.truncate(source.len() - self.crates.len());
+        stacks.stacks[self.target].extend(&self.crates);
+    }
+}
+
+// Commands built by hand get the checks `parse_diagram` does. `command` and `stack` are indices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CraneError {
+    UnknownStack { command: usize, stack: usize },
+    NotEnoughCrates { command: usize, stack: usize, needed: usize, available: usize },
+}
+
+impl std::fmt::Display for CraneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::UnknownStack { command, stack } => write!(f, "Command {command}: no stack {stack}"),
+            Self::NotEnoughCrates { command, stack, needed, available } => write!(
+                f,
+                "Command {command}: moving {needed} crates from stack {stack} which only holds {available}"
+            ),
+        }
+    }
+}
+
+// The starting stacks and every lift a crane made, so any intermediate state can be rebuilt.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rearrangement {
+    pub start: Stacks,
+    pub lifts: Vec<Lift>,
+}
+
+impl Rearrangement {
+    pub fn new(crane: &dyn Crane, stacks: &Stacks, commands: &[Command]) -> Result<Self, CraneError> {
+        let mut current = stacks.clone();
+        let mut lifts = vec![];
+        for (idx, command) in commands.iter().enumerate() {
+            let heights = (current.stacks.get(command.source), current.stacks.get(command.target));
+            let available = match heights {
+                (Some(source), Some(_)) => source.len(),
+                (None, _) => return Err(CraneError::UnknownStack { command: idx, stack: command.source }),
+                (_, None) => return Err(CraneError::UnknownStack { command: idx, stack: command.target }),
+            };
+            if command.amount > available {
+                return Err(CraneError::NotEnoughCrates {
+                    command: idx,
+                    stack: command.source,
+                    needed: command.amount,
+                    available,
+                });
+            }
+            let mut remaining = command.amount;
+            while remaining > 0 {
+                let size = crane.lift_size(remaining).clamp(1, remaining);
+                let source = &current.stacks[command.source];
+                let lift = Lift {
+                    command: idx,
+                    source: command.source,
+                    target: command.target,
+                    crates: source[source.len() - size..].to_vec(),
+                };
+                lift.apply(&mut current);
+                lifts.push(lift);
+                remaining -= size;
+            }
+        }
+        Ok(Rearrangement {
+            start: stacks.clone(),
+            lifts,
+        })
+    }
+
+    // The stacks after the first `lifts` lifts.
+    pub fn replay(&self, lifts: usize) -> Stacks {
+        let mut stacks = self.start.clone();
+        self.lifts
+            .iter()
+            .take(lifts)
+            .for_each(|lift| lift.apply(&mut stacks));
+        stacks
+    }
+
+    // The stacks once the first `commands` commands are done.
+    pub fn after_command(&self, commands: usize) -> Stacks {
+        self.replay(self.lifts.iter().take_while(|lift| lift.command < commands).count())
+    }
+
+    pub fn finish(&self) -> Stacks {
+        self.replay(self.lifts.len())
+    }
+
+    pub fn log(&self) -> Vec<String> {
+        self.lifts
+            .iter()
+            .enumerate()
+            .map(|(idx, lift)| {
+                let crates = lift.crates.iter().map(|c| format!("[{c}]")).collect::<Vec<_>>();
+                format!(
+                    "{:>4} (command {}): {} from {} to {}",
+                    idx + 1,
+                    lift.command + 1,
+                    crates.join(""),
+                    self.start.labels[lift.source],
+                    self.start.labels[lift.target]
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{parse_input, tests::example};
+    use super::*;
+
+    #[test]
+    fn day5_cranes() {
+        let (stacks, commands) = parse_input(example());
+        let limited = Rearrangement::new(&LimitedCrane { capacity: 2 }, &stacks, &commands).unwrap();
+        assert_eq!("CMZ", Rearrangement::new(&CrateMover9000, &stacks, &commands).unwrap().finish().tops());
+        assert_eq!("MCD", Rearrangement::new(&CrateMover9001, &stacks, &commands).unwrap().finish().tops());
+        assert_eq!("MCZ", limited.finish().tops());
+
+        assert_eq!(5, limited.lifts.len());
+        assert_eq!("   2 (command 2): [N][D] from 1 to 3", limited.log()[1]);
+        assert_eq!("[D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3 ", limited.after_command(1).render());
+        assert_eq!(limited.replay(3), limited.after_command(2));
+    }
+
+    #[test]
+    fn day5_crane_errors() {
+        let (stacks, mut commands) = parse_input(example());
+        // Stack 1 holds three crates once the first command is done.
+        commands[1].amount = 4;
+        assert_eq!(
+            Err(CraneError::NotEnoughCrates { command: 1, stack: 0, needed: 4, available: 3 }),
+            Rearrangement::new(&CrateMover9001, &stacks, &commands)
+        );
+        commands[0].target = 3;
+        assert_eq!(
+            Err(CraneError::UnknownStack { command: 0, stack: 3 }),
+            Rearrangement::new(&CrateMover9000, &stacks, &commands)
+        );
+    }
+}
@@ -1,6 +1,8 @@
-use std::{str::FromStr, string::ParseError};
+use crate::utils::download_day;
 
-use aoc_downloader::download_day;
+pub mod crane;
+
+use crane::{CrateMover9000, CrateMover9001, Rearrangement};
 
 const DAY: u32 = 5;
 
@@ -13,68 +15,194 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    MissingLabels,
+    DuplicateLabel(String),
+    Crate { line: usize, column: usize },
+    Command { line: usize, text: String },
+    UnknownStack { line: usize, label: String },
+    NotEnoughCrates { line: usize, stack: String, needed: usize, available: usize },
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::MissingLabels => write!(f, "No stack label row before the commands"),
+            Self::DuplicateLabel(label) => write!(f, "Stack {label} is labeled twice"),
+            Self::Crate { line, column } => write!(f, "Line {line}: no crate fits column {column}"),
+            Self::Command { line, text } => write!(f, "Line {line}: not a command: {text}"),
+            Self::UnknownStack { line, label } => write!(f, "Line {line}: unknown stack {label}"),
+            Self::NotEnoughCrates { line, stack, needed, available } => write!(
+                f,
+                "Line {line}: moving {needed} crates from stack {stack} which only holds {available}"
+            ),
+        }
+    }
+}
+
+// Source and target are indices into the stacks, already resolved from the labels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Command {
     pub amount: usize,
     pub source: usize,
     pub target: usize,
 }
 
-impl FromStr for Command {
-    type Err = ParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let re = regex!(r"move (\d+) from (\d+) to (\d+)");
-        Ok(re
-            .captures(s)
-            .and_then(|captured| {
-                Some(Command {
-                    amount: captured[1].parse::<usize>().ok()?,
-                    source: captured[2].parse::<usize>().ok()?,
-                    target: captured[3].parse::<usize>().ok()?,
-                })
-            })
-            .unwrap())
-    }
+// The stacks in the order of the label row. Crates are listed bottom to top.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stacks {
+    pub labels: Vec<String>,
+    pub stacks: Vec<Vec<char>>,
 }
 
-fn parse_input(input: Vec<String>) -> (Vec<Vec<char>>, Vec<Command>) {
-    let mut switch = true;
-    let mut commands = Vec::new();
-    let mut stack_line = 0;
-    for (idx, line) in input.iter().enumerate() {
-        if switch {
-            if line.is_empty() {
-                stack_line = idx - 1;
-                switch = false;
-                continue;
+// Splits a line into its non-blank words together with the columns they span.
+fn words(line: &str) -> Vec<(usize, usize, &str)> {
+    let mut words = vec![];
+    let mut start = None;
+    for (column, c) in line.char_indices().chain(std::iter::once((line.len(), ' '))) {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some(column),
+            (Some(from), true) => {
+                words.push((from, column, &line[from..column]));
+                start = None;
             }
-        } else {
-            commands.push(Command::from_str(line).unwrap());
+            _ => (),
         }
     }
+    words
+}
+
+impl Stacks {
+    fn parse(lines: &[String]) -> Result<Self, ParseError> {
+        let (label_row, crate_rows) = lines.split_last().ok_or(ParseError::MissingLabels)?;
+        let spans = words(label_row);
+        if spans.is_empty() {
+            return Err(ParseError::MissingLabels);
+        }
+        let labels = spans.iter().map(|&(_, _, label)| label.to_string()).collect::<Vec<_>>();
+        for (idx, label) in labels.iter().enumerate() {
+            if labels[..idx].contains(label) {
+                return Err(ParseError::DuplicateLabel(label.clone()));
+            }
+        }
 
-    let stack_count = input[stack_line]
-        .chars()
-        .filter(|c| !c.is_whitespace())
-        .last()
-        .map(|value| value.to_digit(10).unwrap() as usize)
-        .unwrap();
-
-    let mut stacks = vec![Vec::new(); stack_count];
-    const OFFSET: usize = 1;
-
-    for line in input[0..stack_line].iter().rev() {
-        for (stack_id, stack) in stacks.iter_mut().enumerate().take(stack_count) {
-            if let Some(chest) = line.chars().nth(OFFSET + stack_id * 4) {
-                if chest == ' ' {
-                    continue;
+        // A crate belongs to the stack whose label overlaps its brackets, which also works for
+        // labels wider than a crate.
+        let mut stacks = vec![Vec::new(); labels.len()];
+        for (row, line) in crate_rows.iter().enumerate().rev() {
+            for (start, _, word) in words(line) {
+                let error = ParseError::Crate { line: row + 1, column: start + 1 };
+                let crates = word.as_bytes().chunks(3);
+                if word.len() % 3 != 0 || crates.clone().any(|c| c[0] != b'[' || c[2] != b']') {
+                    return Err(error);
+                }
+                for (offset, chest) in crates.enumerate() {
+                    let from = start + 3 * offset;
+                    let to = from + 3;
+                    let stack = spans
+                        .iter()
+                        .position(|&(first, last, _)| first < to && from < last)
+                        .ok_or_else(|| error.clone())?;
+                    stacks[stack].push(chest[1] as char);
                 }
-                stack.push(chest);
             }
         }
+        Ok(Stacks { labels, stacks })
+    }
+
+    pub fn stack(&self, label: &str) -> Option<usize> {
+        self.labels.iter().position(|l| l == label)
+    }
+
+    pub fn tops(&self) -> String {
+        self.stacks.iter().filter_map(|stack| stack.last()).collect()
+    }
+
+    // Draws the stacks in the puzzle's diagram format, every column as wide as its label but at
+    // least as wide as a crate.
+    pub fn render(&self) -> String {
+        let widths = self.labels.iter().map(|label| label.len().max(3)).collect::<Vec<_>>();
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        let mut lines = vec![];
+        for level in (0..height).rev() {
+            let line = self
+                .stacks
+                .iter()
+                .zip(&widths)
+                .map(|(stack, &width)| match stack.get(level) {
+                    Some(chest) => format!("{:^width$}", format!("[{chest}]")),
+                    None => " ".repeat(width),
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            lines.push(line.trim_end().to_string());
+        }
+        let labels = self
+            .labels
+            .iter()
+            .zip(&widths)
+            .map(|(label, &width)| format!("{label:^width$}"))
+            .collect::<Vec<_>>();
+        lines.push(labels.join(" "));
+        lines.join("\n")
+    }
+}
+
+// Parses the diagram and the commands, checking that every command names existing stacks and
+// never takes more crates than its source holds at that point.
+pub fn parse_diagram(input: &[String]) -> Result<(Stacks, Vec<Command>), ParseError> {
+    let split = input
+        .iter()
+        .position(|line| line.trim().is_empty())
+        .unwrap_or(input.len());
+    let stacks = Stacks::parse(&input[..split])?;
+
+    let re = regex!(r"^\s*move (\d+) from (\S+) to (\S+)\s*$");
+    let mut heights = stacks.stacks.iter().map(Vec::len).collect::<Vec<_>>();
+    let mut commands = vec![];
+    for (idx, text) in input.iter().enumerate().skip(split + 1) {
+        let line = idx + 1;
+        if text.trim().is_empty() {
+            continue;
+        }
+        let captured = re.captures(text).ok_or_else(|| ParseError::Command {
+            line,
+            text: text.clone(),
+        })?;
+        let stack = |label: &str| {
+            stacks.stack(label).ok_or_else(|| ParseError::UnknownStack {
+                line,
+                label: label.to_string(),
+            })
+        };
+        let command = Command {
+            amount: captured[1].parse::<usize>().map_err(|_| ParseError::Command {
+                line,
+                text: text.clone(),
+            })?,
+            source: stack(&captured[2])?,
+            target: stack(&captured[3])?,
+        };
+        if heights[command.source] < command.amount {
+            return Err(ParseError::NotEnoughCrates {
+                line,
+                stack: stacks.labels[command.source].clone(),
+                needed: command.amount,
+                available: heights[command.source],
+            });
+        }
+        heights[command.source] -= command.amount;
+        heights[command.target] += command.amount;
+        commands.push(command);
     }
+    Ok((stacks, commands))
+}
 
-    (stacks, commands)
+pub type Input = (Stacks, Vec<Command>);
+
+pub fn parse_input(input: Vec<String>) -> Input {
+    parse_diagram(&input).unwrap()
 }
 
 pub fn run_day() {
@@ -88,50 +216,14 @@ pub fn run_day() {
     );
 }
 
-fn move_box(com: &Command, stacks: &mut [Vec<char>]) {
-    for _ in 0..com.amount {
-        let tmp = stacks[com.source - 1].pop().unwrap();
-        stacks[com.target - 1].push(tmp);
-    }
-}
-
-fn part1(input: &(Vec<Vec<char>>, Vec<Command>)) -> String {
+pub fn part1(input: &Input) -> String {
     let (stacks, commands) = input;
-    let mut stacks = stacks.clone();
-
-    for com in commands {
-        move_box(com, &mut stacks);
-    }
-
-    let mut output = String::new();
-    for stack in stacks.iter_mut() {
-        output.push(stack.pop().unwrap());
-    }
-    output
-}
-
-fn move_all_boxes(com: &Command, stacks: &mut [Vec<char>]) {
-    let mut tmp = vec![];
-    for _ in 0..com.amount {
-        tmp.push(stacks[com.source - 1].pop().unwrap());
-    }
-    tmp.reverse();
-    stacks[com.target - 1].append(&mut tmp);
+    Rearrangement::new(&CrateMover9000, stacks, commands).unwrap().finish().tops()
 }
 
-fn part2(input: &(Vec<Vec<char>>, Vec<Command>)) -> String {
+pub fn part2(input: &Input) -> String {
     let (stacks, commands) = input;
-    let mut stacks = stacks.clone();
-
-    for com in commands {
-        move_all_boxes(com, &mut stacks);
-    }
-
-    let mut output = String::new();
-    for stack in stacks.iter_mut() {
-        output.push(stack.pop().unwrap());
-    }
-    output
+    Rearrangement::new(&CrateMover9001, stacks, commands).unwrap().finish().tops()
 }
 
 #[cfg(test)]
@@ -149,4 +241,59 @@ mod tests {
         let input = parse_input(get_input());
         assert_eq!("CJVLJQPHS", part2(&input));
     }
+
+    pub fn example() -> Vec<String> {
+        [
+            "    [D]    ",
+            "[N] [C]    ",
+            "[Z] [M] [P]",
+            " 1   2   3 ",
+            "",
+            "move 1 from 2 to 1",
+            "move 3 from 1 to 3",
+            "move 2 from 2 to 1",
+            "move 1 from 1 to 2",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn day5_example() {
+        let input = parse_input(example());
+        assert_eq!("CMZ", part1(&input));
+        assert_eq!("MCD", part2(&input));
+        assert_eq!("    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3 ", input.0.render());
+    }
+
+    #[test]
+    fn day5_wide_labels() {
+        let mut labels = (1..=11).map(|label| label.to_string()).collect::<Vec<_>>();
+        labels[4] = String::from("five");
+        let stacks = Stacks {
+            stacks: (0..11).map(|s| vec!['A'; s % 3]).collect(),
+            labels,
+        };
+        let mut diagram = stacks.render().lines().map(String::from).collect::<Vec<_>>();
+        diagram.push(String::new());
+        diagram.push(String::from("move 1 from five to 11"));
+        let (parsed, commands) = parse_diagram(&diagram).unwrap();
+        assert_eq!(stacks, parsed);
+        assert_eq!(vec![Command { amount: 1, source: 4, target: 10 }], commands);
+
+        diagram.push(String::from("move 3 from 11 to 1"));
+        assert_eq!(
+            Err(ParseError::NotEnoughCrates { line: 6, stack: String::from("11"), needed: 3, available: 2 }),
+            parse_diagram(&diagram)
+        );
+        diagram.pop();
+        diagram.push(String::from("move 1 from 12 to 1"));
+        assert_eq!(
+            Err(ParseError::UnknownStack { line: 6, label: String::from("12") }),
+            parse_diagram(&diagram)
+        );
+        diagram[0] = format!("{:<50}[X]", diagram[0]);
+        assert_eq!(Some(ParseError::Crate { line: 1, column: 51 }), parse_diagram(&diagram).err());
+    }
 }
@@ -1,11 +1,10 @@
-use itertools::Itertools;
-use std::collections::HashMap;
+use std::io::BufRead;
 
-use aoc_downloader::download_day;
+use crate::utils::download_day;
 
 const DAY: u32 = 6;
 
-type Input = char;
+pub type Input = u8;
 
 fn get_input() -> Vec<String> {
     use std::io::BufRead;
@@ -16,8 +15,8 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-fn parse_input(input: Vec<String>) -> Vec<Input> {
-    input[0].chars().collect::<Vec<_>>()
+pub fn parse_input(input: Vec<String>) -> Vec<Input> {
+    input[0].bytes().collect::<Vec<_>>()
 }
 
 pub fn run_day() {
@@ -31,51 +30,102 @@ pub fn run_day() {
     );
 }
 
-fn marker_start(size: usize, input: &[Input]) -> u32 {
-    input
-        .windows(size)
-        .take_while(|window| (*window).iter().unique().count() != size)
-        .count() as u32
-        + size as u32
+pub const START_OF_PACKET: usize = 4;
+pub const START_OF_MESSAGE: usize = 14;
+
+// Tracks the last `size` bytes of a signal together with how often each byte value occurs in
+// them, so every new byte is checked in constant time no matter how large the window is.
+#[derive(Clone, Debug)]
+pub struct MarkerDetector {
+    size: usize,
+    window: Vec<u8>,
+    counts: [usize; 256],
+    repeated: usize,
+    position: usize,
 }
 
-// Worse, more complicated but I was challenged to do it without sets!
-#[allow(dead_code)]
-#[allow(clippy::all)]
-fn alt_marker(size: usize, input: &Vec<Input>) -> u32 {
-    let mut state = HashMap::new();
-    for i in 0..size {
-        state
-            .entry(input[i])
-            .and_modify(|val| *val += 1)
-            .or_insert(1);
+impl MarkerDetector {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "A marker needs at least one byte");
+        MarkerDetector {
+            size,
+            window: vec![0; size],
+            counts: [0; 256],
+            repeated: 0,
+            position: 0,
+        }
     }
-    if state.iter().filter(|(_, count)| **count == 1).count() == size {
-        return size as u32;
+
+    pub fn position(&self) -> usize {
+        self.position
     }
-    for i in size..input.len() {
-        let backmost_char = input[i - size];
-        state.entry(backmost_char).and_modify(|val| *val -= 1);
-        if *state.get(&backmost_char).unwrap() == 0 {
-            state.remove(&backmost_char).unwrap();
+
+    // Feeds the next byte, returns the number of bytes read so far if they end in a marker.
+    pub fn push(&mut self, byte: u8) -> Option<usize> {
+        let slot = self.position % self.size;
+        if self.position >= self.size {
+            let old = self.window[slot] as usize;
+            self.counts[old] -= 1;
+            if self.counts[old] == 1 {
+                self.repeated -= 1;
+            }
         }
-        state
-            .entry(input[i])
-            .and_modify(|val| *val += 1)
-            .or_insert(1);
-        if state.iter().filter(|(_, count)| **count == 1).count() == size {
-            return i as u32 + 1;
+        self.window[slot] = byte;
+        self.counts[byte as usize] += 1;
+        if self.counts[byte as usize] == 2 {
+            self.repeated += 1;
         }
+        self.position += 1;
+
+        (self.position >= self.size && self.repeated == 0).then_some(self.position)
+    }
+}
+
+// Every position at which the last `size` bytes were all different, in order.
+pub struct Markers<I> {
+    bytes: I,
+    detector: MarkerDetector,
+}
+
+impl<I: Iterator<Item = u8>> Markers<I> {
+    pub fn new(size: usize, bytes: I) -> Self {
+        Markers {
+            bytes,
+            detector: MarkerDetector::new(size),
+        }
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for Markers<I> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let detector = &mut self.detector;
+        self.bytes.by_ref().find_map(|byte| detector.push(byte))
     }
-    0
 }
 
-fn part1(input: &[Input]) -> u32 {
-    marker_start(4, input)
+// Streams the signal from `reader`, line breaks are not part of the signal and are skipped.
+pub fn markers_from_reader<R: BufRead>(size: usize, reader: R) -> Markers<impl Iterator<Item = u8>> {
+    Markers::new(
+        size,
+        reader
+            .bytes()
+            .map(|byte| byte.unwrap())
+            .filter(|&byte| byte != b'\n' && byte != b'\r'),
+    )
+}
+
+pub fn marker_start(size: usize, input: &[Input]) -> Option<usize> {
+    Markers::new(size, input.iter().copied()).next()
 }
 
-fn part2(input: &[Input]) -> u32 {
-    marker_start(14, input)
+pub fn part1(input: &[Input]) -> usize {
+    marker_start(START_OF_PACKET, input).unwrap()
+}
+
+pub fn part2(input: &[Input]) -> usize {
+    marker_start(START_OF_MESSAGE, input).unwrap()
 }
 
 #[cfg(test)]
@@ -93,4 +143,26 @@ mod tests {
         let input = parse_input(get_input());
         assert_eq!(3059, part2(&input));
     }
+
+    #[test]
+    fn day6_examples() {
+        let examples = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwtvncz", 5, 23),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+        for (signal, packet, message) in examples {
+            let input = parse_input(vec![signal.to_string()]);
+            assert_eq!(packet, part1(&input));
+            assert_eq!(message, part2(&input));
+        }
+    }
+
+    #[test]
+    fn day6_all_markers() {
+        let markers = markers_from_reader(3, std::io::Cursor::new("aabcbbcda\n")).collect::<Vec<_>>();
+        assert_eq!(vec![4, 8, 9], markers);
+        assert_eq!(Some(1), marker_start(1, b"zz"));
+        assert_eq!(None, marker_start(3, b"abab"));
+    }
 }
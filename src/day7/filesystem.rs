@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+
+use super::Terminal;
+
+pub type DirId = usize;
+
+pub const ROOT: DirId = 0;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct File {
+    pub name: String,
+    pub size: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dir {
+    pub name: String,
+    pub parent: Option<DirId>,
+    pub dirs: Vec<DirId>,
+    pub files: Vec<File>,
+    pub listed: bool,
+}
+
+impl Dir {
+    fn new(name: &str, parent: Option<DirId>) -> Self {
+        Dir {
+            name: name.to_string(),
+            parent,
+            dirs: vec![],
+            files: vec![],
+            listed: false,
+        }
+    }
+}
+
+// Line numbers are counted from one, like in an editor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FsError {
+    UnknownDir { line: usize, name: String },
+    AboveRoot { line: usize },
+    OutputWithoutLs { line: usize },
+    ConflictingListing { line: usize, path: String },
+}
+
+impl std::fmt::Display for FsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::UnknownDir { line, name } => write!(f, "Line {line}: cd into unknown directory {name}"),
+            Self::AboveRoot { line } => write!(f, "Line {line}: cd .. from the root"),
+            Self::OutputWithoutLs { line } => write!(f, "Line {line}: listing without ls"),
+            Self::ConflictingListing { line, path } => {
+                write!(f, "Line {line}: listing of {path} differs from an earlier one")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Node {
+    Dir(DirId),
+    File(usize),
+}
+
+// The directory tree seen in a terminal log. Directories live in one arena and refer to each
+// other by index, the root is always the first one. A directory is only added after its parent,
+// so every parent has a smaller index than its children.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileSystem {
+    dirs: Vec<Dir>,
+    names: HashMap<(DirId, String), Node>,
+}
+
+// What the current `ls` is doing: filling an unlisted directory or repeating an earlier listing,
+// in which case it counts the entries it has seen again.
+enum Listing {
+    None,
+    First(DirId),
+    Repeat(DirId, usize),
+}
+
+impl FileSystem {
+    pub fn from_log(log: &[Terminal]) -> Result<Self, FsError> {
+        let mut fs = FileSystem {
+            dirs: vec![Dir::new("/", None)],
+            names: HashMap::new(),
+        };
+        let mut cwd = ROOT;
+        let mut listing = Listing::None;
+        for (idx, entry) in log.iter().enumerate() {
+            let line = idx + 1;
+            if !matches!(entry, Terminal::Dir(_) | Terminal::File(..)) {
+                fs.finish_listing(&listing, line)?;
+                listing = Listing::None;
+            }
+            match entry {
+                Terminal::Cd(dir) if dir == "/" => cwd = ROOT,
+                Terminal::Cd(dir) if dir == ".." => {
+                    cwd = fs.dirs[cwd].parent.ok_or(FsError::AboveRoot { line })?
+                }
+                Terminal::Cd(dir) => {
+                    cwd = fs.child(cwd, dir).ok_or_else(|| FsError::UnknownDir {
+                        line,
+                        name: dir.clone(),
+                    })?
+                }
+                Terminal::Ls if fs.dirs[cwd].listed => listing = Listing::Repeat(cwd, 0),
+                Terminal::Ls => {
+                    fs.dirs[cwd].listed = true;
+                    listing = Listing::First(cwd);
+                }
+                Terminal::Dir(_) | Terminal::File(..) => fs.list(&mut listing, entry, line)?,
+            }
+        }
+        fs.finish_listing(&listing, log.len() + 1)?;
+        Ok(fs)
+    }
+
+    fn list(&mut self, listing: &mut Listing, entry: &Terminal, line: usize) -> Result<(), FsError> {
+        let conflict = |fs: &Self, dir| FsError::ConflictingListing { line, path: fs.path(dir) };
+        match (listing, entry) {
+            (Listing::None, _) => return Err(FsError::OutputWithoutLs { line }),
+            (Listing::First(dir), Terminal::Dir(name)) => {
+                let dir = *dir;
+                if self.contains(dir, name) {
+                    return Err(conflict(self, dir));
+                }
+                self.dirs.push(Dir::new(name, Some(dir)));
+                let child = self.dirs.len() - 1;
+                self.dirs[dir].dirs.push(child);
+                self.names.insert((dir, name.clone()), Node::Dir(child));
+            }
+            (Listing::First(dir), Terminal::File(name, size)) => {
+                let dir = *dir;
+                if self.contains(dir, name) {
+                    return Err(conflict(self, dir));
+                }
+                self.dirs[dir].files.push(File {
+                    name: name.clone(),
+                    size: *size as u64,
+                });
+                let file = self.dirs[dir].files.len() - 1;
+                self.names.insert((dir, name.clone()), Node::File(file));
+            }
+            (Listing::Repeat(dir, seen), _) => {
+                let known = match entry {
+                    Terminal::Dir(name) => self.child(*dir, name).is_some(),
+                    Terminal::File(name, size) => matches!(
+                        self.names.get(&(*dir, name.clone())),
+                        Some(&Node::File(file)) if self.dirs[*dir].files[file].size == *size as u64
+                    ),
+                    _ => false,
+                };
+                if !known {
+                    return Err(conflict(self, *dir));
+                }
+                *seen += 1;
+            }
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+
+    fn finish_listing(&self, listing: &Listing, line: usize) -> Result<(), FsError> {
+        match *listing {
+            Listing::Repeat(dir, seen) if seen != self.dirs[dir].dirs.len() + self.dirs[dir].files.len() => {
+                Err(FsError::ConflictingListing { line, path: self.path(dir) })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn contains(&self, dir: DirId, name: &str) -> bool {
+        self.names.contains_key(&(dir, name.to_string()))
+    }
+
+    pub fn child(&self, dir: DirId, name: &str) -> Option<DirId> {
+        match self.names.get(&(dir, name.to_string())) {
+            Some(&Node::Dir(child)) => Some(child),
+            _ => None,
+        }
+    }
+
+    pub fn dir(&self, dir: DirId) -> &Dir {
+        &self.dirs[dir]
+    }
+
+    pub fn len(&self) -> usize {
+        self.dirs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dirs.is_empty()
+    }
+
+    pub fn path(&self, dir: DirId) -> String {
+        let mut names = vec![];
+        let mut current = dir;
+        while let Some(parent) = self.dirs[current].parent {
+            names.push(self.dirs[current].name.as_str());
+            current = parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    // Looks up a directory by its absolute path, e.g. `/a/e`.
+    pub fn find(&self, path: &str) -> Option<DirId> {
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(ROOT, |dir, name| self.child(dir, name))
+    }
+
+    // Every file together with the directory holding it.
+    pub fn files(&self) -> impl Iterator<Item = (DirId, &File)> {
+        self.dirs
+            .iter()
+            .enumerate()
+            .flat_map(|(id, dir)| dir.files.iter().map(move |file| (id, file)))
+    }
+
+    // Directories that were entered but never listed, their sizes are a lower bound.
+    pub fn unlisted(&self) -> Vec<DirId> {
+        (0..self.dirs.len()).filter(|&dir| !self.dirs[dir].listed).collect()
+    }
+
+    // The size of a single directory, walking only its own subtree.
+    pub fn size(&self, dir: DirId) -> u64 {
+        let mut size = 0;
+        let mut pending = vec![dir];
+        while let Some(dir) = pending.pop() {
+            size += self.dirs[dir].files.iter().map(|file| file.size).sum::<u64>();
+            pending.extend(&self.dirs[dir].dirs);
+        }
+        size
+    }
+
+    // The total size of every directory, indexed by `DirId`. Children always come after their
+    // parent, so walking the arena backwards finishes every directory before it is added to its
+    // parent, one pass in post-order.
+    pub fn dir_sizes(&self) -> Vec<u64> {
+        let mut sizes = self
+            .dirs
+            .iter()
+            .map(|dir| dir.files.iter().map(|file| file.size).sum::<u64>())
+            .collect::<Vec<_>>();
+        for dir in (0..self.dirs.len()).rev() {
+            if let Some(parent) = self.dirs[dir].parent {
+                sizes[parent] += sizes[dir];
+            }
+        }
+        sizes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{parse_input, tests::example};
+    use super::*;
+
+    fn log(lines: &[&str]) -> Vec<Terminal> {
+        parse_input(lines.iter().map(|line| line.to_string()).collect())
+    }
+
+    #[test]
+    fn day7_filesystem() {
+        let fs = FileSystem::from_log(&parse_input(example())).unwrap();
+        let e = fs.find("/a/e").unwrap();
+        assert_eq!("/a/e", fs.path(e));
+        assert_eq!(584, fs.size(e));
+        assert_eq!(48381165, fs.size(ROOT));
+        assert_eq!(10, fs.files().count());
+        assert_eq!(Some(fs.find("/a").unwrap()), fs.dir(e).parent);
+        assert_eq!(None, fs.find("/a/x"));
+        assert!(fs.unlisted().is_empty());
+    }
+
+    #[test]
+    fn day7_filesystem_errors() {
+        assert_eq!(
+            Err(FsError::UnknownDir { line: 3, name: String::from("b") }),
+            FileSystem::from_log(&log(&["$ cd /", "$ ls", "$ cd b"]))
+        );
+        assert_eq!(
+            Err(FsError::AboveRoot { line: 2 }),
+            FileSystem::from_log(&log(&["$ cd /", "$ cd .."]))
+        );
+        assert_eq!(
+            Err(FsError::OutputWithoutLs { line: 2 }),
+            FileSystem::from_log(&log(&["$ cd /", "12 a.txt"]))
+        );
+        let listing = ["$ cd /", "$ ls", "dir a", "12 b", "$ cd a", "$ cd ..", "$ ls", "dir a"];
+        assert_eq!(
+            Err(FsError::ConflictingListing { line: 9, path: String::from("/") }),
+            FileSystem::from_log(&log(&listing))
+        );
+        assert_eq!(
+            Err(FsError::ConflictingListing { line: 9, path: String::from("/") }),
+            FileSystem::from_log(&log(&[&listing[..], &["13 b"]].concat()))
+        );
+        assert!(FileSystem::from_log(&log(&[&listing[..], &["12 b"]].concat())).is_ok());
+        assert_eq!(
+            Err(FsError::ConflictingListing { line: 4, path: String::from("/") }),
+            FileSystem::from_log(&log(&["$ cd /", "$ ls", "dir a", "4 a"]))
+        );
+    }
+}
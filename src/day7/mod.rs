@@ -1,8 +1,12 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
 use std::{num::ParseIntError, str::FromStr};
 
-use aoc_downloader::download_day;
+use crate::utils::download_day;
+
+pub mod filesystem;
+pub mod report;
+
+use filesystem::FileSystem;
+use report::Disk;
 
 const DAY: u32 = 7;
 
@@ -16,7 +20,7 @@ fn get_input() -> Vec<String> {
 }
 
 #[derive(Debug)]
-enum Terminal {
+pub enum Terminal {
     Cd(String),
     Ls,
     Dir(String),
@@ -57,9 +61,9 @@ impl FromStr for Terminal {
     }
 }
 
-type Input = Terminal;
+pub type Input = Terminal;
 
-fn parse_input(input: Vec<String>) -> Vec<Input> {
+pub fn parse_input(input: Vec<String>) -> Vec<Input> {
     input
         .iter()
         .map(|v| Terminal::from_str(v).unwrap())
@@ -77,69 +81,48 @@ pub fn run_day() {
     );
 }
 
-fn get_dir_paths(input: &Vec<Input>) -> HashMap<PathBuf, u32> {
-    let mut dir_sizes: HashMap<PathBuf, u32> = HashMap::new();
-    let mut cur_path = PathBuf::new();
-    for line in input {
-        match line {
-            Terminal::Cd(dir) if dir == ".." => cur_path = cur_path.parent().unwrap().to_owned(),
-            Terminal::Cd(dir) => {
-                cur_path = cur_path.join(dir);
-                dir_sizes.entry(cur_path.clone()).or_insert(0);
-            }
-            Terminal::Ls => (),
-            Terminal::Dir(dir) => {
-                dir_sizes.entry(cur_path.join(dir)).or_insert(0);
-            }
-            Terminal::File(_, size) => {
-                dir_sizes
-                    .entry(cur_path.clone())
-                    .and_modify(|dsize| *dsize += size)
-                    .or_insert(*size);
-            }
+// A log exploring a tree in which every directory above `depth` holds `width` subdirectories, and
+// every directory holds `files` files of 1000 bytes. A width of one gives a single deep chain.
+pub fn synthetic_log(width: usize, depth: usize, files: usize) -> Vec<String> {
+    let listing = |log: &mut Vec<String>, level: usize| {
+        log.push(String::from("$ ls"));
+        if level < depth {
+            log.extend((0..width).map(|dir| format!("dir d{dir}")));
         }
-    }
-    dir_sizes
-}
-
-fn size_dirs(dirs: HashMap<PathBuf, u32>) -> HashMap<PathBuf, u32> {
-    let mut dirs = dirs;
-    let fix_dir = dirs.clone();
-    for dir in &mut dirs {
-        for other_dir in &fix_dir {
-            if other_dir.0 == dir.0 {
-                continue;
-            }
-            if other_dir.0.as_path().starts_with(dir.0.as_path()) {
-                *dir.1 += other_dir.1;
+        log.extend((0..files).map(|file| format!("1000 f{file}.txt")));
+    };
+
+    let mut log = vec![String::from("$ cd /")];
+    listing(&mut log, 0);
+    // The next subdirectory to enter for every directory on the current path.
+    let mut path = vec![0];
+    while let Some(&next) = path.last() {
+        let level = path.len() - 1;
+        if level < depth && next < width {
+            log.push(format!("$ cd d{next}"));
+            path[level] += 1;
+            path.push(0);
+            listing(&mut log, level + 1);
+        } else {
+            path.pop();
+            if !path.is_empty() {
+                log.push(String::from("$ cd .."));
             }
         }
     }
-    dirs
+    log
 }
 
-fn part1(input: &Vec<Input>) -> u32 {
-    let dir_sizes = get_dir_paths(input);
-    let dir_sizes = size_dirs(dir_sizes);
+pub fn part1(input: &[Input]) -> u64 {
+    let fs = FileSystem::from_log(input).unwrap();
 
-    dir_sizes.values().filter(|size| **size <= 100_000).sum()
+    fs.dir_sizes().into_iter().filter(|&size| size <= 100_000).sum()
 }
 
-fn part2(input: &Vec<Input>) -> u32 {
-    const AVAILABLE: u32 = 70_000_000;
-    const REQUIRED: u32 = 30_000_000;
-
-    let dir_sizes = get_dir_paths(input);
-    let dir_sizes = size_dirs(dir_sizes);
+pub fn part2(input: &[Input]) -> u64 {
+    let fs = FileSystem::from_log(input).unwrap();
 
-    let total_size = dir_sizes.get(&PathBuf::from("/")).unwrap().to_owned();
-    let unused = AVAILABLE - total_size;
-
-    *dir_sizes
-        .values()
-        .filter(|size| (unused + **size) >= REQUIRED)
-        .min()
-        .unwrap()
+    report::plan_cleanup(&fs, Disk::default()).unwrap().freed
 }
 
 #[cfg(test)]
@@ -157,4 +140,55 @@ mod tests {
         let input = parse_input(get_input());
         assert_eq!(4978279, part2(&input));
     }
+
+    pub fn example() -> Vec<String> {
+        [
+            "$ cd /",
+            "$ ls",
+            "dir a",
+            "14848514 b.txt",
+            "8504156 c.dat",
+            "dir d",
+            "$ cd a",
+            "$ ls",
+            "dir e",
+            "29116 f",
+            "2557 g",
+            "62596 h.lst",
+            "$ cd e",
+            "$ ls",
+            "584 i",
+            "$ cd ..",
+            "$ cd ..",
+            "$ cd d",
+            "$ ls",
+            "4060174 j",
+            "8033020 d.log",
+            "5626152 d.ext",
+            "7214296 k",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn day7_example() {
+        let input = parse_input(example());
+        assert_eq!(95437, part1(&input));
+        assert_eq!(24933642, part2(&input));
+    }
+
+    #[test]
+    fn day7_synthetic_log() {
+        let fs = FileSystem::from_log(&parse_input(synthetic_log(3, 4, 2))).unwrap();
+        let sizes = fs.dir_sizes();
+        assert_eq!(1 + 3 + 9 + 27 + 81, fs.len());
+        assert_eq!(121 * 2000, sizes[0]);
+        assert_eq!(40 * 2000, sizes[fs.find("/d2").unwrap()]);
+        assert!((0..fs.len()).all(|dir| sizes[dir] == fs.size(dir)));
+
+        let chain = FileSystem::from_log(&parse_input(synthetic_log(1, 10_000, 1))).unwrap();
+        assert_eq!(10_001_000, chain.dir_sizes()[0]);
+    }
 }
@@ -0,0 +1,293 @@
+use std::fmt::Write;
+
+use super::filesystem::{DirId, FileSystem, ROOT};
+
+// One line per directory, largest first: `size<TAB>path`.
+pub fn du(fs: &FileSystem) -> String {
+    let sizes = fs.dir_sizes();
+    let mut dirs = (0..fs.len()).collect::<Vec<_>>();
+    dirs.sort_by_key(|&dir| (std::cmp::Reverse(sizes[dir]), fs.path(dir)));
+    let mut report = String::new();
+    for dir in dirs {
+        writeln!(report, "{}\t{}", sizes[dir], fs.path(dir)).unwrap();
+    }
+    report
+}
+
+// The listing from the puzzle text, entries sorted by name and directories with their total size.
+pub fn tree(fs: &FileSystem) -> String {
+    let sizes = fs.dir_sizes();
+    let mut report = String::new();
+    let mut stack = vec![(0, Entry::Dir(ROOT))];
+    while let Some((depth, entry)) = stack.pop() {
+        let indent = "  ".repeat(depth);
+        match entry {
+            Entry::Dir(dir) => {
+                writeln!(report, "{indent}- {} (dir, size={})", fs.dir(dir).name, sizes[dir]).unwrap();
+                let mut children = fs
+                    .dir(dir)
+                    .dirs
+                    .iter()
+                    .map(|&child| (fs.dir(child).name.as_str(), Entry::Dir(child)))
+                    .chain(
+                        fs.dir(dir)
+                            .files
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, file)| (file.name.as_str(), Entry::File(dir, idx))),
+                    )
+                    .collect::<Vec<_>>();
+                children.sort_by(|a, b| b.0.cmp(a.0));
+                stack.extend(children.into_iter().map(|(_, child)| (depth + 1, child)));
+            }
+            Entry::File(dir, idx) => {
+                let file = &fs.dir(dir).files[idx];
+                writeln!(report, "{indent}- {} (file, size={})", file.name, file.size).unwrap();
+            }
+        }
+    }
+    report
+}
+
+#[derive(Clone, Copy)]
+enum Entry {
+    Dir(DirId),
+    File(DirId, usize),
+}
+
+// The `n` largest files by full path, largest first.
+pub fn largest_files(fs: &FileSystem, n: usize) -> Vec<(String, u64)> {
+    let mut files = fs
+        .files()
+        .map(|(dir, file)| {
+            let path = fs.path(dir);
+            let separator = if path.ends_with('/') { "" } else { "/" };
+            (format!("{path}{separator}{}", file.name), file.size)
+        })
+        .collect::<Vec<_>>();
+    files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    files.truncate(n);
+    files
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Disk {
+    pub capacity: u64,
+    pub required: u64,
+}
+
+impl Default for Disk {
+    fn default() -> Self {
+        Disk {
+            capacity: 70_000_000,
+            required: 30_000_000,
+        }
+    }
+}
+
+impl Disk {
+    // How many bytes have to go before `required` bytes are free.
+    pub fn to_free(&self, used: u64) -> u64 {
+        self.required.saturating_sub(self.capacity.saturating_sub(used))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Plan {
+    pub dirs: Vec<DirId>,
+    pub freed: u64,
+    // False if the search hit its step limit, a plan with fewer directories or freeing less may
+    // then exist.
+    pub exhaustive: bool,
+}
+
+// Picking directories is a subset sum, past this many search steps the planner settles for the
+// best plan found so far. Every candidate looked at costs a step, and one more for every chosen
+// directory it is checked against.
+const SEARCH_LIMIT: usize = 1_000_000;
+
+// Entry and exit times of a depth first walk. Two spans are either disjoint or one holds the
+// other, exactly when its directory contains the other one.
+fn spans(fs: &FileSystem) -> Vec<(usize, usize)> {
+    let mut spans = vec![(0, 0); fs.len()];
+    let mut clock = 0;
+    let mut stack = vec![(ROOT, false)];
+    while let Some((dir, done)) = stack.pop() {
+        clock += 1;
+        if done {
+            spans[dir].1 = clock;
+            continue;
+        }
+        spans[dir].0 = clock;
+        stack.push((dir, true));
+        stack.extend(fs.dir(dir).dirs.iter().map(|&child| (child, false)));
+    }
+    spans
+}
+
+struct Planner {
+    spans: Vec<(usize, usize)>,
+    // Largest first, with the sum of the sizes before every candidate.
+    candidates: Vec<(DirId, u64)>,
+    prefix: Vec<u64>,
+    needed: u64,
+    chosen: Vec<DirId>,
+    best: Option<(Vec<DirId>, u64)>,
+    steps: usize,
+}
+
+impl Planner {
+    fn nested(&self, a: DirId, b: DirId) -> bool {
+        let (a, b) = (self.spans[a], self.spans[b]);
+        a.0 < b.1 && b.0 < a.1
+    }
+
+    // Picks exactly `picks` more directories from `candidates[start..]`.
+    fn search(&mut self, start: usize, picks: usize, freed: u64) {
+        if freed >= self.needed {
+            if !matches!(&self.best, Some((_, best)) if *best <= freed) {
+                self.best = Some((self.chosen.clone(), freed));
+            }
+            return;
+        }
+        if picks == 0 || matches!(&self.best, Some((_, best)) if *best <= freed) {
+            return;
+        }
+        for idx in start..self.candidates.len() {
+            let cost = 1 + self.chosen.len();
+            if self.steps < cost {
+                self.steps = 0;
+                return;
+            }
+            self.steps -= cost;
+            // The next `picks` candidates bound what this branch can free, and later candidates
+            // are no larger, so once they fall short every following one does too.
+            let end = (idx + picks).min(self.candidates.len());
+            if freed + self.prefix[end] - self.prefix[idx] < self.needed {
+                break;
+            }
+            let (dir, size) = self.candidates[idx];
+            if self.chosen.iter().any(|&other| self.nested(other, dir)) {
+                continue;
+            }
+            self.chosen.push(dir);
+            self.search(idx + 1, picks - 1, freed + size);
+            self.chosen.pop();
+        }
+    }
+}
+
+// The fewest directories to delete to reach the disk's required free space, and among those the
+// ones freeing the least. No chosen directory contains another. The root would always do on its
+// own, so it's only chosen when no other directories free enough.
+pub fn plan_cleanup(fs: &FileSystem, disk: Disk) -> Option<Plan> {
+    plan_with_limit(fs, disk, SEARCH_LIMIT)
+}
+
+fn plan_with_limit(fs: &FileSystem, disk: Disk, limit: usize) -> Option<Plan> {
+    let sizes = fs.dir_sizes();
+    let needed = disk.to_free(sizes[ROOT]);
+    if needed == 0 {
+        return Some(Plan { dirs: vec![], freed: 0, exhaustive: true });
+    }
+
+    let mut candidates = (0..fs.len())
+        .filter(|&dir| dir != ROOT)
+        .map(|dir| (dir, sizes[dir]))
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|&(dir, size)| (std::cmp::Reverse(size), dir));
+    let mut prefix = vec![0];
+    prefix.extend(candidates.iter().scan(0, |sum, &(_, size)| {
+        *sum += size;
+        Some(*sum)
+    }));
+    let mut planner = Planner {
+        spans: spans(fs),
+        candidates,
+        prefix,
+        needed,
+        chosen: vec![],
+        best: None,
+        steps: limit,
+    };
+    for picks in 1..=planner.candidates.len() {
+        planner.search(0, picks, 0);
+        if planner.best.is_some() || planner.steps == 0 {
+            break;
+        }
+    }
+    let exhaustive = planner.steps > 0;
+    match planner.best {
+        Some((dirs, freed)) => Some(Plan { dirs, freed, exhaustive }),
+        None if sizes[ROOT] >= needed => Some(Plan { dirs: vec![ROOT], freed: sizes[ROOT], exhaustive }),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{parse_input, synthetic_log, tests::example};
+    use super::*;
+
+    #[test]
+    fn day7_reports() {
+        let fs = FileSystem::from_log(&parse_input(example())).unwrap();
+        assert_eq!("48381165\t/\n24933642\t/d\n94853\t/a\n584\t/a/e\n", du(&fs));
+        let tree = tree(&fs);
+        let lines = tree.lines().collect::<Vec<_>>();
+        assert_eq!("- / (dir, size=48381165)", lines[0]);
+        assert_eq!("  - a (dir, size=94853)", lines[1]);
+        assert_eq!("      - i (file, size=584)", lines[3]);
+        assert_eq!("    - k (file, size=7214296)", lines[13]);
+        assert_eq!(
+            vec![(String::from("/b.txt"), 14848514), (String::from("/c.dat"), 8504156)],
+            largest_files(&fs, 2)
+        );
+    }
+
+    #[test]
+    fn day7_cleanup() {
+        let fs = FileSystem::from_log(&parse_input(example())).unwrap();
+        let d = fs.find("/d").unwrap();
+        let e = fs.find("/a/e").unwrap();
+        let plan = |dirs, freed| Some(Plan { dirs, freed, exhaustive: true });
+        assert_eq!(plan(vec![d], 24933642), plan_cleanup(&fs, Disk::default()));
+
+        let disk = Disk { capacity: 70_000_000, required: 46_552_535 };
+        assert_eq!(plan(vec![d, e], 24934226), plan_cleanup(&fs, disk));
+        // Only the root, with the files right in it, frees this much.
+        let disk = Disk { capacity: 70_000_000, required: 46_652_535 };
+        assert_eq!(plan(vec![ROOT], 48381165), plan_cleanup(&fs, disk));
+        let disk = Disk { capacity: 70_000_000, required: 70_000_001 };
+        assert_eq!(None, plan_cleanup(&fs, disk));
+        let disk = Disk { capacity: 70_000_000, required: 1 };
+        assert_eq!(plan(vec![], 0), plan_cleanup(&fs, disk));
+    }
+
+    #[test]
+    fn day7_cleanup_limit() {
+        // 20 directories of 21 KB with 20 of 1 KB each, picking more than one is needed and the
+        // small ones are tempting but always inside a picked one.
+        let fs = FileSystem::from_log(&parse_input(synthetic_log(20, 2, 1))).unwrap();
+        let top = |name: &str| fs.find(name).unwrap();
+        let disk = Disk { capacity: 1_000_000, required: 1_000_000 - 421_000 + 62_500 };
+        let plan = plan_cleanup(&fs, disk).unwrap();
+        assert_eq!(vec![top("/d0"), top("/d1"), top("/d2")], plan.dirs);
+        assert_eq!((63_000, true), (plan.freed, plan.exhaustive));
+
+        let plan = plan_with_limit(&fs, disk, 5).unwrap();
+        assert!(!plan.exhaustive);
+        assert!(plan.freed >= 62_500);
+
+        // In a chain every directory contains the next, so no two can be picked together, but the
+        // sizes alone never rule a pick count out. The limit has to stop the search.
+        let chain = FileSystem::from_log(&parse_input(synthetic_log(1, 600, 1))).unwrap();
+        let disk = Disk { capacity: 601_000, required: 600_500 };
+        let start = std::time::Instant::now();
+        let plan = plan_cleanup(&chain, disk).unwrap();
+        assert!(start.elapsed() < std::time::Duration::from_secs(2), "{:?}", start.elapsed());
+        assert_eq!((vec![ROOT], 601_000, false), (plan.dirs, plan.freed, plan.exhaustive));
+        let plan = plan_with_limit(&chain, disk, 1000).unwrap();
+        assert_eq!((vec![ROOT], false), (plan.dirs, plan.exhaustive));
+    }
+}
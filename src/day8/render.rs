@@ -0,0 +1,79 @@
+use std::fmt::Write;
+
+use super::visibility::Forest;
+use crate::image::{self, darken, heat, Image, Rgb};
+
+// Hidden trees are drawn at this fraction of their brightness.
+const HIDDEN: f64 = 0.35;
+
+// Scenic scores grow as a product of four distances, the square root spreads the colours out.
+fn shade(forest: &Forest, row: usize, column: usize, best: u64) -> Rgb {
+    let cell = forest.cell(row, column);
+    let colour = heat((cell.scenic_score() as f64 / best.max(1) as f64).sqrt());
+    if cell.is_visible() {
+        colour
+    } else {
+        darken(colour, HIDDEN)
+    }
+}
+
+// Every tree is printed as its height on a background coloured by its scenic score. Visible trees
+// are bold, hidden ones dimmed and the best spot is inverted.
+pub fn ansi(forest: &Forest) -> String {
+    let best = forest.best_spot();
+    let best_score = best.map_or(0, |(_, score)| score);
+    let mut out = String::new();
+    for row in 0..forest.height {
+        for column in 0..forest.width {
+            let cell = forest.cell(row, column);
+            let [r, g, b] = shade(forest, row, column, best_score);
+            let style = if Some((row, column)) == best.map(|(spot, _)| spot) {
+                "7;1"
+            } else if cell.is_visible() {
+                "1"
+            } else {
+                "2"
+            };
+            write!(out, "\x1b[0;{style};38;2;255;255;255;48;2;{r};{g};{b}m{}", cell.height).unwrap();
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+// Every tree becomes a `scale` x `scale` block, the best spot is drawn white.
+pub fn heat_map(forest: &Forest, scale: usize) -> Image {
+    let scale = scale.max(1);
+    let best = forest.best_spot();
+    let best_score = best.map_or(0, |(_, score)| score);
+    let mut image = Image::new(forest.width * scale, forest.height * scale, image::BLACK);
+    for ((row, column), _) in forest.cells() {
+        let colour = if Some((row, column)) == best.map(|(spot, _)| spot) {
+            image::WHITE
+        } else {
+            shade(forest, row, column, best_score)
+        };
+        image.fill_cell(column, row, scale, colour);
+    }
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{parse_input, tests::example};
+    use super::*;
+
+    #[test]
+    fn day8_heat_map() {
+        let forest = Forest::new(&parse_input(example())).unwrap();
+        let image = heat_map(&forest, 2);
+        assert_eq!((10, 10), (image.width, image.height));
+        assert_eq!(image::WHITE, image.get(5, 7));
+        assert_eq!(heat(0.0), image.get(0, 0));
+        assert_eq!(darken(heat((1.0f64 / 8.0).sqrt()), HIDDEN), image.get(6, 2));
+
+        let ansi = ansi(&forest);
+        assert_eq!(5, ansi.lines().count());
+        assert!(ansi.lines().nth(3).unwrap().contains("\x1b[0;7;1;"));
+    }
+}
@@ -0,0 +1,157 @@
+use super::Input;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+}
+
+// What one tree sees. Both arrays are indexed by `Direction as usize`: whether the tree can be
+// seen from that edge, and how many trees it can see looking towards it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Cell {
+    pub height: i32,
+    pub visible_from: [bool; 4],
+    pub view: [u32; 4],
+}
+
+impl Cell {
+    pub fn is_visible(&self) -> bool {
+        self.visible_from.iter().any(|&visible| visible)
+    }
+
+    pub fn scenic_score(&self) -> u64 {
+        self.view.iter().map(|&distance| distance as u64).product()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RaggedRow {
+    pub row: usize,
+    pub len: usize,
+    pub expected: usize,
+}
+
+// Visibility of every tree in a rectangular forest. Each row and column is swept once per
+// direction with a stack of the trees not yet hidden behind a taller one, so the whole analysis
+// is linear in the number of trees.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Forest {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Forest {
+    pub fn new(grid: &[Input]) -> Result<Self, RaggedRow> {
+        let width = grid.first().map_or(0, Vec::len);
+        if let Some((row, line)) = grid.iter().enumerate().find(|(_, line)| line.len() != width) {
+            return Err(RaggedRow { row, len: line.len(), expected: width });
+        }
+        let mut forest = Forest {
+            width,
+            height: grid.len(),
+            cells: grid
+                .iter()
+                .flatten()
+                .map(|&height| Cell { height, ..Cell::default() })
+                .collect(),
+        };
+
+        for row in 0..forest.height {
+            let line = (0..width).map(|column| row * width + column).collect::<Vec<_>>();
+            forest.sweep(Direction::West, line.iter().copied());
+            forest.sweep(Direction::East, line.iter().rev().copied());
+        }
+        for column in 0..width {
+            let line = (0..forest.height).map(|row| row * width + column).collect::<Vec<_>>();
+            forest.sweep(Direction::North, line.iter().copied());
+            forest.sweep(Direction::South, line.iter().rev().copied());
+        }
+        Ok(forest)
+    }
+
+    // Walks a line starting at the edge `direction` points to. Every tree pops the lower trees off
+    // the stack, whatever remains on top is the first tree blocking its view.
+    fn sweep(&mut self, direction: Direction, line: impl Iterator<Item = usize>) {
+        let mut stack: Vec<(u32, i32)> = vec![];
+        for (position, cell) in line.enumerate() {
+            let position = position as u32;
+            let height = self.cells[cell].height;
+            while let Some(&(_, top)) = stack.last() {
+                if top >= height {
+                    break;
+                }
+                stack.pop();
+            }
+            let cell = &mut self.cells[cell];
+            match stack.last() {
+                Some(&(blocker, _)) => cell.view[direction as usize] = position - blocker,
+                None => {
+                    cell.view[direction as usize] = position;
+                    cell.visible_from[direction as usize] = true;
+                }
+            }
+            stack.push((position, height));
+        }
+    }
+
+    pub fn cell(&self, row: usize, column: usize) -> &Cell {
+        &self.cells[row * self.width + column]
+    }
+
+    // Every tree with its row and column, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &Cell)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| ((idx / self.width, idx % self.width), cell))
+    }
+
+    pub fn visible_count(&self) -> usize {
+        self.cells.iter().filter(|cell| cell.is_visible()).count()
+    }
+
+    // The tree with the highest scenic score, the first one in reading order on ties.
+    pub fn best_spot(&self) -> Option<((usize, usize), u64)> {
+        self.cells()
+            .map(|(position, cell)| (position, cell.scenic_score()))
+            .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{parse_input, tests::example};
+    use super::*;
+
+    #[test]
+    fn day8_visibility() {
+        let forest = Forest::new(&parse_input(example())).unwrap();
+        assert_eq!(21, forest.visible_count());
+        assert_eq!(Some(((3, 2), 8)), forest.best_spot());
+        let cell = forest.cell(1, 2);
+        assert_eq!([true, true, false, false], cell.visible_from);
+        assert_eq!([1, 2, 2, 1], cell.view);
+        assert_eq!(4, cell.scenic_score());
+    }
+
+    #[test]
+    fn day8_rectangular_forest() {
+        let grid = parse_input(vec!["30373".into(), "25512".into(), "65332".into()]);
+        let forest = Forest::new(&grid).unwrap();
+        assert_eq!((5, 3), (forest.width, forest.height));
+        assert_eq!(14, forest.visible_count());
+        assert_eq!(Some(((1, 2), 2)), forest.best_spot());
+        assert_eq!(
+            Err(RaggedRow { row: 1, len: 4, expected: 5 }),
+            Forest::new(&[grid[0].clone(), grid[1][..4].to_vec()])
+        );
+    }
+}
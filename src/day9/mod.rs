@@ -1,6 +1,8 @@
-use std::{str::FromStr, num::ParseIntError, collections::HashSet};
+use std::{str::FromStr, num::ParseIntError, collections::{HashMap, HashSet}};
 
-use aoc_downloader::download_day;
+use crate::utils::download_day;
+
+pub mod render;
 
 const DAY: u32 = 9;
 
@@ -13,47 +15,47 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Input = Command;
-type Output = usize;
+pub type Input = Command;
+pub type Output = usize;
+
+// `x` grows to the right and `y` upwards.
+pub type Position = (isize, isize);
 
-enum Command {
-    Right(usize),
-    Left(usize),
-    Down(usize),
-    Up(usize),
+// Moves the head `steps` times by (`dx`, `dy`), one cell straight or diagonal per step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Command {
+    pub dx: isize,
+    pub dy: isize,
+    pub steps: usize,
 }
 
 impl FromStr for Command {
     type Err = ParseIntError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let re = regex!(r"(\w) (\d+)");
-
-        Ok(re.captures(s).map(|captured| {
-            let step = captured[2].parse::<usize>().unwrap();
-            match &captured[1] {
-                "R" => Command::Right(step),
-                "L" => Command::Left(step),
-                "D" => Command::Down(step),
-                "U" => Command::Up(step),
-                e => panic!("Unkown input: {e}"),
-            }
-        }).unwrap())
-    }
-}
-
-impl Command {
-    pub fn get_inner(&self) -> usize {
-        match self {
-            Command::Right(inner) => *inner,
-            Command::Left(inner) => *inner,
-            Command::Down(inner) => *inner,
-            Command::Up(inner) => *inner,
-        }
+        let re = regex!(r"([UDLR]+) (\d+)");
+
+        let captured = re.captures(s).unwrap_or_else(|| panic!("Unkown input: {s}"));
+        let (dx, dy) = match &captured[1] {
+            "R" => (1, 0),
+            "L" => (-1, 0),
+            "D" => (0, -1),
+            "U" => (0, 1),
+            "UR" | "RU" => (1, 1),
+            "UL" | "LU" => (-1, 1),
+            "DR" | "RD" => (1, -1),
+            "DL" | "LD" => (-1, -1),
+            e => panic!("Unkown input: {e}"),
+        };
+        Ok(Command {
+            dx,
+            dy,
+            steps: captured[2].parse::<usize>()?,
+        })
     }
 }
 
-fn parse_input(input: Vec<String>) -> Vec<Input> {
+pub fn parse_input(input: Vec<String>) -> Vec<Input> {
     input
         .iter()
         .map(|v| Command::from_str(v).unwrap())
@@ -66,65 +68,102 @@ pub fn run_day() {
     println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
 }
 
-type OpsIsize = fn(isize, isize) -> isize;
-
-fn add(lhs: isize, rhs: isize) -> isize {
-    lhs + rhs
-}
-
-fn sub(lhs: isize, rhs: isize) -> isize {
-    lhs - rhs
+fn touching(a: Position, b: Position) -> bool {
+    (a.0 - b.0).abs() <= 1 && (a.1 - b.1).abs() <= 1
 }
 
-fn mul(lhs: isize, rhs: isize) -> isize {
-    lhs * rhs
+// A rope of knots starting on the same cell, the first knot is the head. Every cell any knot
+// occupies is recorded, per knot and for the whole rope.
+#[derive(Clone, Debug)]
+pub struct Rope {
+    knots: Vec<Position>,
+    visited: Vec<HashSet<Position>>,
+    trails: Vec<Vec<Position>>,
+    first_visits: HashMap<Position, usize>,
 }
 
-fn pull_rope(rope: &mut Vec<(isize, isize)>, direction: (OpsIsize, OpsIsize)) {
-    rope[0] = (direction.0(rope[0].0, 1), direction.1(rope[0].1, 1));
-    for i in 1..rope.len(){
-        if let Some(new_rope_pos) = update_tail(&rope[i-1], &rope[i]) {
-            rope[i] = new_rope_pos;
+impl Rope {
+    pub fn new(knots: usize) -> Self {
+        assert!(knots > 0, "A rope needs a head");
+        Rope {
+            knots: vec![(0, 0); knots],
+            visited: vec![HashSet::from([(0, 0)]); knots],
+            trails: vec![vec![(0, 0)]; knots],
+            first_visits: HashMap::from([((0, 0), 0)]),
         }
     }
-}
 
-fn update_tail(head: &(isize, isize), tail: &(isize, isize)) -> Option<(isize, isize)> {
-    match (head.0 - tail.0, head.1 - tail.1) {
-        (0, 0) | (1,0) | (-1,0) | (1, 1) | (1, -1) | (-1, 1) | (-1, -1) | (0, 1) | (0, -1) => {
-            None
-        },
-        (x, y) => {
-            Some((tail.0 + x.signum(), tail.1 + y.signum()))
-        },
+    fn record(&mut self, knot: usize, position: Position) {
+        self.knots[knot] = position;
+        self.visited[knot].insert(position);
+        self.trails[knot].push(position);
+        self.first_visits.entry(position).or_insert(knot);
     }
-}
 
-fn follow_n_rope(input: &[Input], length: usize) -> usize {
-    let mut visited = HashSet::new();
-    let mut rope: Vec<(isize, isize)> = vec![(0, 0); length];
-    visited.insert(rope[length - 1]);
-    for command in input {
-        let ops: (OpsIsize, OpsIsize)  = match command {
-            Command::Right(_) => (add, mul),
-            Command::Left(_) => (sub, mul),
-            Command::Down(_) => (mul, sub),
-            Command::Up(_) => (mul, add),
-        };
-        let step = command.get_inner();
-        for _ in 0..step {
-            pull_rope(&mut rope, ops);
-            visited.insert(rope[length - 1]);
+    // Moves the head by (`dx`, `dy`). A move longer than one cell is a jump, each following knot
+    // then keeps stepping towards the knot before it until they touch again.
+    pub fn step(&mut self, dx: isize, dy: isize) {
+        let (x, y) = self.knots[0];
+        self.record(0, (x + dx, y + dy));
+        for knot in 1..self.knots.len() {
+            let leader = self.knots[knot - 1];
+            let mut position = self.knots[knot];
+            while !touching(leader, position) {
+                position.0 += (leader.0 - position.0).signum();
+                position.1 += (leader.1 - position.1).signum();
+                self.record(knot, position);
+            }
+        }
+    }
+
+    pub fn apply(&mut self, command: &Command) {
+        for _ in 0..command.steps {
+            self.step(command.dx, command.dy);
         }
     }
-    visited.len()
+
+    pub fn knots(&self) -> &[Position] {
+        &self.knots
+    }
+
+    pub fn head(&self) -> Position {
+        self.knots[0]
+    }
+
+    pub fn tail(&self) -> Position {
+        self.knots[self.knots.len() - 1]
+    }
+
+    pub fn visited(&self, knot: usize) -> &HashSet<Position> {
+        &self.visited[knot]
+    }
+
+    // Every position `knot` moved to, in order and starting with the origin.
+    pub fn trail(&self, knot: usize) -> &[Position] {
+        &self.trails[knot]
+    }
+
+    // The knot that reached `cell` first. Within one step the head moves before the knots behind it.
+    pub fn first_visitor(&self, cell: Position) -> Option<usize> {
+        self.first_visits.get(&cell).copied()
+    }
+
+    pub fn first_visits(&self) -> &HashMap<Position, usize> {
+        &self.first_visits
+    }
 }
 
-fn part1(input: &[Input]) -> Output {
+pub fn follow_n_rope(input: &[Input], length: usize) -> usize {
+    let mut rope = Rope::new(length);
+    input.iter().for_each(|command| rope.apply(command));
+    rope.visited(length - 1).len()
+}
+
+pub fn part1(input: &[Input]) -> Output {
     follow_n_rope(input, 2)
 }
 
-fn part2(input: &[Input]) -> Output {
+pub fn part2(input: &[Input]) -> Output {
     follow_n_rope(input, 10)
 }
 
@@ -143,4 +182,33 @@ mod tests {
         let input = parse_input(get_input());
         assert_eq!(2504, part2(&input));
     }
+
+    pub fn commands(lines: &str) -> Vec<Input> {
+        parse_input(lines.split(',').map(String::from).collect())
+    }
+
+    #[test]
+    fn day9_examples() {
+        let input = commands("R 4,U 4,L 3,D 1,R 4,D 1,L 5,R 2");
+        assert_eq!(13, part1(&input));
+        assert_eq!(1, part2(&input));
+        assert_eq!(36, part2(&commands("R 5,U 8,L 8,D 3,R 17,D 10,L 25,U 20")));
+    }
+
+    #[test]
+    fn day9_rope_moves() {
+        let mut rope = Rope::new(2);
+        commands("UR 3").iter().for_each(|command| rope.apply(command));
+        assert_eq!((3, 3), rope.head());
+        assert_eq!(&[(0, 0), (1, 1), (2, 2)], rope.trail(1));
+
+        let mut rope = Rope::new(3);
+        rope.step(4, 0);
+        assert_eq!(&[(4, 0), (3, 0), (2, 0)], rope.knots());
+        assert_eq!(&[(0, 0), (1, 0), (2, 0)], rope.trail(2));
+        assert_eq!(4, rope.visited(1).len());
+        assert_eq!(Some(0), rope.first_visitor((0, 0)));
+        assert_eq!(Some(1), rope.first_visitor((3, 0)));
+        assert_eq!(None, rope.first_visitor((5, 0)));
+    }
 }
@@ -0,0 +1,184 @@
+use super::{Command, Position, Rope};
+use crate::image::{self, heat, Image};
+
+// The smallest rectangle holding every cell seen so far, it only ever grows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Viewport {
+    pub min: Position,
+    pub max: Position,
+}
+
+impl Viewport {
+    pub fn include(&mut self, (x, y): Position) {
+        self.min = (self.min.0.min(x), self.min.1.min(y));
+        self.max = (self.max.0.max(x), self.max.1.max(y));
+    }
+
+    // Every cell any knot of `rope` has been on.
+    pub fn around(rope: &Rope) -> Self {
+        let mut viewport = Viewport::default();
+        rope.first_visits().keys().for_each(|&cell| viewport.include(cell));
+        viewport
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.0 - self.min.0 + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.1 - self.min.1 + 1) as usize
+    }
+
+    // The rows from the top down, `y` grows upwards like in the puzzle.
+    fn rows(&self) -> impl Iterator<Item = isize> {
+        (self.min.1..=self.max.1).rev()
+    }
+
+    fn columns(&self) -> impl Iterator<Item = isize> {
+        self.min.0..=self.max.0
+    }
+}
+
+// The head is `H`, the tail of a two knot rope `T`, every other knot its index, with letters
+// after 9.
+fn label(knot: usize, len: usize) -> char {
+    match knot {
+        0 => 'H',
+        _ if len == 2 => 'T',
+        _ => char::from_digit(knot as u32 % 36, 36).unwrap(),
+    }
+}
+
+// One frame in the puzzle's format: the knots, covering the ones behind them, the start `s`,
+// cells the tail has visited `#` and everything else `.`.
+pub fn frame(rope: &Rope, viewport: &Viewport) -> String {
+    let knots = rope.knots();
+    let tail = rope.visited(knots.len() - 1);
+    let mut out = String::new();
+    for y in viewport.rows() {
+        for x in viewport.columns() {
+            out.push(match knots.iter().position(|&knot| knot == (x, y)) {
+                Some(knot) => label(knot, knots.len()),
+                None if (x, y) == (0, 0) => 's',
+                None if tail.contains(&(x, y)) => '#',
+                None => '.',
+            });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+// The cells `knot` visited, cropped to them and the start.
+pub fn visited_pattern(rope: &Rope, knot: usize) -> String {
+    let visited = rope.visited(knot);
+    let mut viewport = Viewport::default();
+    visited.iter().for_each(|&cell| viewport.include(cell));
+    let mut out = String::new();
+    for y in viewport.rows() {
+        for x in viewport.columns() {
+            out.push(match (x, y) {
+                (0, 0) => 's',
+                cell if visited.contains(&cell) => '#',
+                _ => '.',
+            });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+// Steps a rope through commands, drawing a frame after every step. The viewport grows with the
+// rope, so earlier frames can be smaller than later ones.
+#[derive(Clone, Debug)]
+pub struct Animation {
+    pub rope: Rope,
+    pub viewport: Viewport,
+}
+
+impl Animation {
+    pub fn new(knots: usize) -> Self {
+        Animation {
+            rope: Rope::new(knots),
+            viewport: Viewport::default(),
+        }
+    }
+
+    pub fn apply(&mut self, command: &Command) -> Vec<String> {
+        (0..command.steps)
+            .map(|_| {
+                self.rope.step(command.dx, command.dy);
+                self.rope.knots().iter().for_each(|&knot| self.viewport.include(knot));
+                frame(&self.rope, &self.viewport)
+            })
+            .collect()
+    }
+}
+
+// Every visited cell becomes a `scale` x `scale` block coloured by the knot that reached it first,
+// from blue for the head to red for the tail. The knots' final positions are white.
+pub fn trail_image(rope: &Rope, scale: usize) -> Image {
+    let scale = scale.max(1);
+    let viewport = Viewport::around(rope);
+    let knots = rope.knots().len();
+    let mut image = Image::new(viewport.width() * scale, viewport.height() * scale, image::BLACK);
+    let cell = |(x, y): Position| ((x - viewport.min.0) as usize, (viewport.max.1 - y) as usize);
+    for (&position, &knot) in rope.first_visits() {
+        let (x, y) = cell(position);
+        image.fill_cell(x, y, scale, heat(knot as f64 / (knots - 1).max(1) as f64));
+    }
+    for &position in rope.knots() {
+        let (x, y) = cell(position);
+        image.fill_cell(x, y, scale, image::WHITE);
+    }
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::commands;
+    use super::*;
+
+    #[test]
+    fn day9_frames() {
+        let mut animation = Animation::new(2);
+        let frames = animation.apply(&commands("R 4")[0]);
+        assert_eq!(vec!["TH\n", "sTH\n", "s#TH\n", "s##TH\n"], frames);
+
+        commands("U 4,L 3,D 1,R 4,D 1,L 5,R 2")
+            .iter()
+            .for_each(|command| _ = animation.apply(command));
+        assert_eq!((6, 5), (animation.viewport.width(), animation.viewport.height()));
+        assert_eq!("..##..\n...##.\n.TH##.\n....#.\ns###..\n", frame(&animation.rope, &animation.viewport));
+        assert_eq!("..##.\n...##\n.####\n....#\ns###.\n", visited_pattern(&animation.rope, 1));
+    }
+
+    #[test]
+    fn day9_trail_image() {
+        let mut rope = Rope::new(10);
+        commands("R 5,U 8,L 8,D 3,R 17,D 10,L 25,U 20")
+            .iter()
+            .for_each(|command| rope.apply(command));
+        let pattern = [
+            "#.....................",
+            "#.............###.....",
+            "#............#...#....",
+            ".#..........#.....#...",
+            "..#..........#.....#..",
+            "...#........#.......#.",
+            "....#......s.........#",
+            ".....#..............#.",
+            "......#............#..",
+            ".......#..........#...",
+            "........#........#....",
+            ".........########.....",
+        ];
+        assert_eq!(pattern.map(|line| format!("{line}\n")).concat(), visited_pattern(&rope, 9));
+
+        let image = trail_image(&rope, 2);
+        let viewport = Viewport::around(&rope);
+        assert_eq!((2 * viewport.width(), 2 * viewport.height()), (image.width, image.height));
+        assert_eq!(image::WHITE, image.get(2 * (-11 - viewport.min.0) as usize, 2 * (viewport.max.1 - 15) as usize));
+        assert_eq!(heat(0.0), image.get(2 * (1 - viewport.min.0) as usize, 2 * viewport.max.1 as usize));
+    }
+}
@@ -1,4 +1,4 @@
-use aoc_downloader::download_day;
+use crate::utils::download_day;
 
 const DAY: u32 = 0;
 
@@ -11,10 +11,10 @@ fn get_input() -> Vec<String> {
     reader.lines().collect::<Result<_, _>>().unwrap()
 }
 
-type Input = u64;
-type Output = u64;
+pub type Input = u64;
+pub type Output = u64;
 
-fn parse_input(input: Vec<String>) -> Vec<Input> {
+pub fn parse_input(input: Vec<String>) -> Vec<Input> {
     input
         .iter()
         .map(|v| v.parse::<u64>().unwrap())
@@ -27,7 +27,7 @@ pub fn run_day() {
     println!("Running day {}:\n\tPart1 {}\n\tPart2 {}", DAY, part1(&input), part2(&input));
 }
 
-fn part1(input: &[Input]) -> Output {
+pub fn part1(input: &[Input]) -> Output {
     for val1 in input {
         for val2 in input {
             if 2020 == (val1 + val2) {
@@ -38,7 +38,7 @@ fn part1(input: &[Input]) -> Output {
     0
 }
 
-fn part2(input: &[Input]) -> Output {
+pub fn part2(input: &[Input]) -> Output {
     for val1 in input {
         for val2 in input {
             for val3 in input {
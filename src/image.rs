@@ -0,0 +1,197 @@
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Png,
+    Ppm,
+}
+
+// A plain RGB raster for the visualisations. It writes PPM, and PNG without any compression, so
+// no image crate is needed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        self.pixels[y * self.width + x] = colour;
+    }
+
+    // Paints the `size` x `size` block of cell (`x`, `y`), for grids drawn with big pixels.
+    pub fn fill_cell(&mut self, x: usize, y: usize, size: usize, colour: Rgb) {
+        for py in y * size..(y + 1) * size {
+            for px in x * size..(x + 1) * size {
+                self.set(px, py, colour);
+            }
+        }
+    }
+
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend(self.pixels.iter().flatten());
+        ppm
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut header = Vec::with_capacity(13);
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // 8 bit RGB, default compression and filtering, no interlacing.
+        header.extend([8, 2, 0, 0, 0]);
+
+        // Every scanline starts with filter type 0.
+        let mut raw = Vec::with_capacity(self.height * (3 * self.width + 1));
+        for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            raw.push(0);
+            raw.extend(row.iter().flatten());
+        }
+
+        let mut png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        chunk(&mut png, b"IHDR", &header);
+        chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    pub fn encode(&self, format: Format) -> Vec<u8> {
+        match format {
+            Format::Png => self.to_png(),
+            Format::Ppm => self.to_ppm(),
+        }
+    }
+
+    // Writes `format` whatever the file is called.
+    pub fn write(&self, path: &std::path::Path, format: Format) -> std::io::Result<()> {
+        std::fs::write(path, self.encode(format))
+    }
+
+    // Writes a PNG or, for a `.ppm` file, a PPM.
+    pub fn save(&self, path: &std::path::Path) -> std::io::Result<()> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ppm") => self.write(path, Format::Ppm),
+            _ => self.write(path, Format::Png),
+        }
+    }
+}
+
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+// A zlib stream made of uncompressed deflate blocks, each holding at most 65535 bytes.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let blocks = data.chunks(0xffff).collect::<Vec<_>>();
+    if blocks.is_empty() {
+        stream.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    for (idx, block) in blocks.iter().enumerate() {
+        let last = (idx + 1 == blocks.len()) as u8;
+        let len = block.len() as u16;
+        stream.push(last);
+        stream.extend(len.to_le_bytes());
+        stream.extend((!len).to_le_bytes());
+        stream.extend(*block);
+    }
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+pub fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+// Maps 0.0 to dark blue and 1.0 to red, passing cyan, green and yellow.
+pub fn heat(value: f64) -> Rgb {
+    const STOPS: [Rgb; 5] = [[0, 0, 128], [0, 200, 255], [0, 200, 0], [255, 230, 0], [220, 0, 0]];
+    let position = value.clamp(0.0, 1.0) * (STOPS.len() - 1) as f64;
+    let idx = (position as usize).min(STOPS.len() - 2);
+    let t = position - idx as f64;
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    [
+        mix(STOPS[idx][0], STOPS[idx + 1][0]),
+        mix(STOPS[idx][1], STOPS[idx + 1][1]),
+        mix(STOPS[idx][2], STOPS[idx + 1][2]),
+    ]
+}
+
+pub fn darken(colour: Rgb, factor: f64) -> Rgb {
+    colour.map(|channel| (channel as f64 * factor).round() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(0xcbf4_3926, crc32(b"123456789"));
+        assert_eq!(0x11e6_0398, adler32(b"Wikipedia"));
+    }
+
+    #[test]
+    fn png_layout() {
+        let mut image = Image::new(2, 1, BLACK);
+        image.set(1, 0, WHITE);
+        let png = image.to_png();
+        assert_eq!(b"IHDR", &png[12..16]);
+        assert_eq!([0, 0, 0, 2, 0, 0, 0, 1], png[16..24]);
+        // IDAT: zlib header, one final stored block of the 7 filtered bytes, adler32.
+        let idat = &png[37..41 + 2 + 5 + 7 + 4];
+        assert_eq!(b"IDAT", &idat[..4]);
+        assert_eq!([0x78, 0x01, 1, 7, 0, 0xf8, 0xff], idat[4..11]);
+        assert_eq!([0, 0, 0, 0, 255, 255, 255], idat[11..18]);
+        assert_eq!(b"IEND", &png[png.len() - 8..png.len() - 4]);
+        assert_eq!(b"P6\n2 1\n255\n\0\0\0\xff\xff\xff".to_vec(), image.to_ppm());
+    }
+
+    #[test]
+    fn explicit_format() {
+        let image = Image::new(1, 1, WHITE);
+        let path = std::env::temp_dir().join(format!("aoc-2022-{}.img", std::process::id()));
+        image.write(&path, Format::Ppm).unwrap();
+        assert_eq!(image.to_ppm(), std::fs::read(&path).unwrap());
+        image.write(&path, Format::Png).unwrap();
+        assert_eq!(image.encode(Format::Png), std::fs::read(&path).unwrap());
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,61 +1,94 @@
 #![feature(int_roundings)]
 #![feature(generators, generator_trait)]
 #[macro_use]
-
 mod utils;
 
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-
-pub fn get_days() -> Vec<fn()> {
-    vec![
-        day1::run_day,
-        day2::run_day,
-        day3::run_day,
-        day4::run_day,
-        day5::run_day,
-        day6::run_day,
-        day7::run_day,
-        day8::run_day,
-        day9::run_day,
-        day10::run_day,
-        day11::run_day,
-        day12::run_day,
-        day13::run_day,
-        day14::run_day,
-        day15::run_day,
-        day16::run_day,
-        day17::run_day,
-        day18::run_day,
-        day19::run_day,
-        day20::run_day,
-        day21::run_day,
-        day22::run_day,
-        day23::run_day,
-        day24::run_day,
-        day25::run_day,
-    ]
+pub mod bigint;
+pub mod image;
+pub mod memo;
+pub mod stats;
+pub mod watch;
+
+// Declares every day behind its cargo feature. Disabled days keep their slot in `get_days` so the
+// day number still maps to the right index.
+macro_rules! days {
+    ($($day:ident: $feature:literal),* $(,)?) => {
+        $(
+            #[cfg(feature = $feature)]
+            pub mod $day;
+
+            #[cfg(not(feature = $feature))]
+            mod $day {
+                pub fn run_day() {
+                    println!("Skipping {}: built without the `{}` feature", stringify!($day), $feature);
+                }
+            }
+        )*
+
+        pub fn get_days() -> Vec<fn()> {
+            vec![
+                $($day::run_day,)*
+            ]
+        }
+
+        mod solvers {
+            use super::Solver;
+
+            $(
+                #[cfg(feature = $feature)]
+                pub fn $day() -> Option<Solver> {
+                    Some(Solver {
+                        part1: |input| super::$day::part1(&super::$day::parse_input(input)).to_string(),
+                        part2: |input| super::$day::part2(&super::$day::parse_input(input)).to_string(),
+                    })
+                }
+
+                #[cfg(not(feature = $feature))]
+                pub fn $day() -> Option<Solver> {
+                    None
+                }
+            )*
+        }
+
+        pub fn get_solvers() -> Vec<Option<Solver>> {
+            vec![
+                $(solvers::$day(),)*
+            ]
+        }
+    };
+}
+
+// Runs a single part on already loaded input lines, without going through `run_day`.
+#[derive(Clone, Copy)]
+pub struct Solver {
+    pub part1: fn(Vec<String>) -> String,
+    pub part2: fn(Vec<String>) -> String,
+}
+
+days! {
+    day1: "day1",
+    day2: "day2",
+    day3: "day3",
+    day4: "day4",
+    day5: "day5",
+    day6: "day6",
+    day7: "day7",
+    day8: "day8",
+    day9: "day9",
+    day10: "day10",
+    day11: "day11",
+    day12: "day12",
+    day13: "day13",
+    day14: "day14",
+    day15: "day15",
+    day16: "day16",
+    day17: "day17",
+    day18: "day18",
+    day19: "day19",
+    day20: "day20",
+    day21: "day21",
+    day22: "day22",
+    day23: "day23",
+    day24: "day24",
+    day25: "day25",
 }
@@ -1,13 +1,173 @@
 #![feature(generators, generator_trait)]
 use std::env;
+use std::path::PathBuf;
+
+use aoc_2022::stats;
+
+fn run(day: usize, call: fn()) {
+    let _phase = stats::phase(day as u32, "total");
+    call();
+}
+
+// watch <day> [part] [--example <file>]
+fn watch(args: &[String]) -> ! {
+    let day = args[0].parse::<usize>().unwrap();
+    let mut part = None;
+    let mut example = None;
+    let mut args = args[1..].iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--example" => example = args.next().map(PathBuf::from),
+            p => part = Some(p.parse::<u8>().unwrap()),
+        }
+    }
+    let solver = aoc_2022::get_solvers()[day - 1].unwrap_or_else(|| panic!("Day {day} is not enabled"));
+    aoc_2022::watch::watch(day, solver, part, example)
+}
+
+// inventory [--json] [file]
+#[cfg(feature = "day1")]
+fn inventory(args: &[String]) {
+    use aoc_2022::day1::{inventories_from_reader, report::InventoryReport};
+
+    let json = args.iter().any(|arg| arg == "--json");
+    let file = args
+        .iter()
+        .find(|arg| !arg.starts_with("--"))
+        .map_or(PathBuf::from("input/input1.txt"), PathBuf::from);
+    let reader = std::io::BufReader::new(std::fs::File::open(file).unwrap());
+    let report = InventoryReport::new(inventories_from_reader(reader), 10).expect("No elves in inventory");
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        print!("{}", report.to_table());
+    }
+}
+
+// forest [--png <file>] [--ppm <file>] [--scale <n>] [file]
+#[cfg(feature = "day8")]
+fn forest(args: &[String]) {
+    use aoc_2022::day8::{parse_input, render, visibility::Forest};
+    use aoc_2022::image::Format;
+    use std::io::BufRead;
+
+    let mut images = vec![];
+    let mut scale = 8;
+    let mut file = PathBuf::from("input/input8.txt");
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--png" => images.extend(args.next().map(|path| (PathBuf::from(path), Format::Png))),
+            "--ppm" => images.extend(args.next().map(|path| (PathBuf::from(path), Format::Ppm))),
+            "--scale" => scale = args.next().unwrap().parse::<usize>().unwrap(),
+            f => file = PathBuf::from(f),
+        }
+    }
+    let reader = std::io::BufReader::new(std::fs::File::open(file).unwrap());
+    let grid = parse_input(reader.lines().collect::<Result<_, _>>().unwrap());
+    let forest = Forest::new(&grid).unwrap_or_else(|e| panic!("Forest is not rectangular: {e:?}"));
+    if images.is_empty() {
+        print!("{}", render::ansi(&forest));
+    }
+    for (path, format) in images {
+        render::heat_map(&forest, scale).write(&path, format).unwrap();
+    }
+}
+
+// rope [--knots <n>] [--frames] [--png <file>] [--ppm <file>] [--scale <n>] [file]
+#[cfg(feature = "day9")]
+fn rope(args: &[String]) {
+    use aoc_2022::day9::{parse_input, render, Rope};
+    use aoc_2022::image::Format;
+    use std::io::BufRead;
+
+    let mut knots = 10;
+    let mut frames = false;
+    let mut images = vec![];
+    let mut scale = 4;
+    let mut file = PathBuf::from("input/input9.txt");
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--knots" => knots = args.next().unwrap().parse::<usize>().unwrap(),
+            "--frames" => frames = true,
+            "--png" => images.extend(args.next().map(|path| (PathBuf::from(path), Format::Png))),
+            "--ppm" => images.extend(args.next().map(|path| (PathBuf::from(path), Format::Ppm))),
+            "--scale" => scale = args.next().unwrap().parse::<usize>().unwrap(),
+            f => file = PathBuf::from(f),
+        }
+    }
+    let reader = std::io::BufReader::new(std::fs::File::open(file).unwrap());
+    let lines = reader.lines().collect::<Result<Vec<_>, _>>().unwrap();
+    let commands = parse_input(lines.clone());
+    let rope = if frames {
+        let mut animation = render::Animation::new(knots);
+        for (line, command) in lines.iter().zip(&commands) {
+            println!("== {line} ==\n");
+            for frame in animation.apply(command) {
+                println!("{frame}");
+            }
+        }
+        animation.rope
+    } else {
+        let mut rope = Rope::new(knots);
+        commands.iter().for_each(|command| rope.apply(command));
+        rope
+    };
+    if images.is_empty() {
+        print!("{}", render::visited_pattern(&rope, knots - 1));
+    }
+    for (path, format) in images {
+        render::trail_image(&rope, scale).write(&path, format).unwrap();
+    }
+}
 
 fn main() {
-    let args = env::args().collect::<Vec<String>>();
-    if 2 == args.len() {
-        aoc_2022::get_days()[args[1].parse::<usize>().unwrap() - 1]();
+    let mut args = env::args().skip(1).collect::<Vec<String>>();
+    let stats_format = args
+        .iter()
+        .position(|arg| arg.starts_with("--stats"))
+        .map(|idx| args.remove(idx));
+    match stats_format.as_deref() {
+        Some("--stats") | Some("--stats=json") => stats::enable(),
+        Some(e) => panic!("Unknown option: {e}"),
+        None => (),
+    }
+
+    if Some("watch") == args.first().map(String::as_str) {
+        watch(&args[1..]);
+    }
+    #[cfg(feature = "day1")]
+    if Some("inventory") == args.first().map(String::as_str) {
+        inventory(&args[1..]);
+        return;
+    }
+
+    #[cfg(feature = "day8")]
+    if Some("forest") == args.first().map(String::as_str) {
+        forest(&args[1..]);
+        return;
+    }
+
+    #[cfg(feature = "day9")]
+    if Some("rope") == args.first().map(String::as_str) {
+        rope(&args[1..]);
+        return;
+    }
+
+    let days = aoc_2022::get_days();
+    if 1 == args.len() {
+        let day = args[0].parse::<usize>().unwrap();
+        run(day, days[day - 1]);
     } else {
-        for call in aoc_2022::get_days() {
-            call();
+        for (idx, call) in days.into_iter().enumerate() {
+            run(idx + 1, call);
         }
     }
+
+    match stats_format.as_deref() {
+        Some("--stats=json") => println!("{}", stats::to_json(&stats::snapshot())),
+        Some(_) => print!("{}", stats::to_table(&stats::snapshot())),
+        None => (),
+    }
 }
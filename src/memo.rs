@@ -0,0 +1,142 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub entries: usize,
+}
+
+impl MemoStats {
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+// Lookup table for recursive solvers. A memo is owned by whoever runs the search, so it lives
+// exactly as long as one solve. A bounded memo throws away its entries once it is full instead of
+// growing without limit.
+#[derive(Debug)]
+pub struct Memo<K, V> {
+    table: HashMap<K, V>,
+    bound: Option<usize>,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            table: HashMap::new(),
+            bound: None,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bounded(max_entries: usize) -> Self {
+        Memo {
+            bound: Some(max_entries),
+            ..Self::default()
+        }
+    }
+
+    pub fn get<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let found = self.table.get(key).cloned();
+        if found.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        found
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        if let Some(bound) = self.bound {
+            if self.table.len() >= bound && !self.table.contains_key(&key) {
+                self.evictions += self.table.len() as u64;
+                self.table.clear();
+            }
+        }
+        self.table.insert(key, value);
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.table.remove(key)
+    }
+
+    // Drops all entries but keeps the statistics, so a solver can reuse one memo across runs.
+    pub fn clear(&mut self) {
+        self.table.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+            entries: self.table.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memo_counts_hits_and_misses() {
+        let mut memo = Memo::new();
+        assert_eq!(None, memo.get(&1));
+        memo.insert(1, "one");
+        assert_eq!(Some("one"), memo.get(&1));
+        assert_eq!(Some("one"), memo.get(&1));
+        assert_eq!(MemoStats { hits: 2, misses: 1, evictions: 0, entries: 1 }, memo.stats());
+    }
+
+    #[test]
+    fn bounded_memo_flushes_when_full() {
+        let mut memo = Memo::bounded(2);
+        memo.insert(1, 1);
+        memo.insert(2, 2);
+        memo.insert(2, 4);
+        assert_eq!(2, memo.len());
+        memo.insert(3, 9);
+        assert_eq!(1, memo.len());
+        assert_eq!(None, memo.get(&1));
+        assert_eq!(Some(9), memo.get(&3));
+        assert_eq!(2, memo.stats().evictions);
+    }
+}
@@ -0,0 +1,168 @@
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::memo::MemoStats;
+
+// Collection is off unless the binary asks for it, so solvers can report unconditionally.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static REPORT: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value {
+    Count(u64),
+    Max(u64),
+    Time(Duration),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub day: u32,
+    pub name: String,
+    pub value: Value,
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+fn record(day: u32, name: &str, value: Value) {
+    if !is_enabled() {
+        return;
+    }
+    let mut report = REPORT.lock().unwrap();
+    if let Some(entry) = report.iter_mut().find(|e| e.day == day && e.name == name) {
+        entry.value = match (entry.value, value) {
+            (Value::Count(old), Value::Count(new)) => Value::Count(old + new),
+            (Value::Max(old), Value::Max(new)) => Value::Max(old.max(new)),
+            (Value::Time(old), Value::Time(new)) => Value::Time(old + new),
+            (_, new) => new,
+        };
+    } else {
+        report.push(Entry { day, name: name.to_string(), value });
+    }
+}
+
+// Adds `amount` to a counter, e.g. states visited by a search.
+pub fn count(day: u32, name: &str, amount: u64) {
+    record(day, name, Value::Count(amount));
+}
+
+// Keeps the largest value seen, e.g. the widest BFS frontier.
+pub fn maximum(day: u32, name: &str, value: u64) {
+    record(day, name, Value::Max(value));
+}
+
+pub fn memo(day: u32, name: &str, stats: &MemoStats) {
+    count(day, &format!("{name} memo hits"), stats.hits);
+    count(day, &format!("{name} memo misses"), stats.misses);
+    count(day, &format!("{name} memo evictions"), stats.evictions);
+}
+
+pub struct Phase {
+    day: u32,
+    name: &'static str,
+    start: Instant,
+}
+
+impl Drop for Phase {
+    fn drop(&mut self) {
+        record(self.day, self.name, Value::Time(self.start.elapsed()));
+    }
+}
+
+// Times the enclosing scope, bind it to a named variable: `let _phase = stats::phase(DAY, "part1");`
+pub fn phase(day: u32, name: &'static str) -> Phase {
+    Phase {
+        day,
+        name,
+        start: Instant::now(),
+    }
+}
+
+pub fn snapshot() -> Vec<Entry> {
+    let mut report = REPORT.lock().unwrap().clone();
+    report.sort_by_key(|entry| entry.day);
+    report
+}
+
+pub fn reset() {
+    REPORT.lock().unwrap().clear();
+}
+
+pub fn to_table(report: &[Entry]) -> String {
+    let width = report.iter().map(|e| e.name.len()).max().unwrap_or(0);
+    let mut table = String::new();
+    let mut day = None;
+    for entry in report {
+        if day != Some(entry.day) {
+            day = Some(entry.day);
+            writeln!(table, "Day {}:", entry.day).unwrap();
+        }
+        let value = match entry.value {
+            Value::Count(n) => n.to_string(),
+            Value::Max(n) => format!("{n} (max)"),
+            Value::Time(t) => format!("{:.3}ms", t.as_secs_f64() * 1000.0),
+        };
+        writeln!(table, "\t{:width$}  {}", entry.name, value).unwrap();
+    }
+    table
+}
+
+pub fn to_json(report: &[Entry]) -> String {
+    let entries = report
+        .iter()
+        .map(|entry| {
+            let (kind, value) = match entry.value {
+                Value::Count(n) => ("count", n.to_string()),
+                Value::Max(n) => ("max", n.to_string()),
+                Value::Time(t) => ("seconds", t.as_secs_f64().to_string()),
+            };
+            format!(
+                "{{\"day\":{},\"name\":\"{}\",\"kind\":\"{}\",\"value\":{}}}",
+                entry.day,
+                entry.name.replace('\\', "\\\\").replace('"', "\\\""),
+                kind,
+                value
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", entries.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The report is global, so each test uses its own day number.
+    fn entries(day: u32) -> Vec<Entry> {
+        snapshot().into_iter().filter(|e| e.day == day).collect()
+    }
+
+    #[test]
+    fn counters_accumulate() {
+        enable();
+        count(101, "states", 3);
+        count(101, "states", 4);
+        maximum(101, "frontier", 7);
+        maximum(101, "frontier", 2);
+        assert_eq!(
+            vec![
+                Entry { day: 101, name: "states".to_string(), value: Value::Count(7) },
+                Entry { day: 101, name: "frontier".to_string(), value: Value::Max(7) },
+            ],
+            entries(101)
+        );
+    }
+
+    #[test]
+    fn json_output() {
+        let report = vec![Entry { day: 102, name: "rounds".to_string(), value: Value::Count(10) }];
+        assert_eq!(r#"[{"day":102,"name":"rounds","kind":"count","value":10}]"#, to_json(&report));
+    }
+}
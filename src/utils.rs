@@ -1,6 +1,17 @@
+#[allow(unused_macros)]
 macro_rules! regex {
     ($re:literal $(,)?) => {{
         static RE: once_cell::sync::OnceCell<regex::Regex> = once_cell::sync::OnceCell::new();
         RE.get_or_init(|| regex::Regex::new($re).unwrap())
     }};
 }
+
+#[cfg(feature = "download")]
+pub use aoc_downloader::download_day;
+
+// Without the downloader the puzzle inputs have to be placed in `input/` by hand.
+#[cfg(not(feature = "download"))]
+#[allow(dead_code)]
+pub fn download_day(_day: u32, _path: &str) -> Result<(), std::convert::Infallible> {
+    Ok(())
+}
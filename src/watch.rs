@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::BufRead;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::Solver;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    pub file: PathBuf,
+    pub part: u8,
+    pub answer: Result<String, String>,
+    pub previous: Option<Result<String, String>>,
+}
+
+impl fmt::Display for Run {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let show = |answer: &Result<String, String>| match answer {
+            Ok(answer) => answer.clone(),
+            Err(e) => format!("failed ({e})"),
+        };
+        write!(f, "{} part{}: ", self.file.display(), self.part)?;
+        match &self.previous {
+            None => write!(f, "{}", show(&self.answer)),
+            Some(previous) if *previous == self.answer => write!(f, "{} (unchanged)", show(&self.answer)),
+            Some(previous) => write!(f, "{} -> {}", show(previous), show(&self.answer)),
+        }
+    }
+}
+
+// Re-runs the selected parts of one day whenever one of the watched files changes. Changes are
+// detected by modification time and size, so nothing is read until a file was touched.
+pub struct Watcher {
+    solver: Solver,
+    parts: Vec<u8>,
+    files: Vec<PathBuf>,
+    seen: HashMap<PathBuf, Option<(SystemTime, u64)>>,
+    answers: HashMap<(PathBuf, u8), Result<String, String>>,
+}
+
+impl Watcher {
+    pub fn new(solver: Solver, part: Option<u8>, files: Vec<PathBuf>) -> Self {
+        Watcher {
+            solver,
+            parts: part.map_or(vec![1, 2], |part| vec![part]),
+            files,
+            seen: HashMap::new(),
+            answers: HashMap::new(),
+        }
+    }
+
+    fn fingerprint(file: &Path) -> Option<(SystemTime, u64)> {
+        let meta = std::fs::metadata(file).ok()?;
+        Some((meta.modified().ok()?, meta.len()))
+    }
+
+    fn solve(&self, part: u8, input: Vec<String>) -> Result<String, String> {
+        let solve = match part {
+            1 => self.solver.part1,
+            2 => self.solver.part2,
+            e => return Err(format!("Unknown part: {e}")),
+        };
+        panic::catch_unwind(|| solve(input)).map_err(|e| {
+            e.downcast_ref::<&str>()
+                .map(|msg| msg.to_string())
+                .or_else(|| e.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| String::from("panicked"))
+        })
+    }
+
+    // Runs every selected part on each file that changed since the last poll.
+    pub fn poll(&mut self) -> Vec<Run> {
+        let mut runs = vec![];
+        for file in self.files.clone() {
+            let fingerprint = Self::fingerprint(&file);
+            if self.seen.get(&file) == Some(&fingerprint) {
+                continue;
+            }
+            self.seen.insert(file.clone(), fingerprint);
+
+            let input = std::fs::File::open(&file)
+                .and_then(|f| std::io::BufReader::new(f).lines().collect::<Result<Vec<_>, _>>())
+                .map_err(|e| e.to_string());
+            for &part in &self.parts {
+                let answer = input.clone().and_then(|input| self.solve(part, input));
+                let previous = self.answers.insert((file.clone(), part), answer.clone());
+                runs.push(Run {
+                    file: file.clone(),
+                    part,
+                    answer,
+                    previous,
+                });
+            }
+        }
+        runs
+    }
+}
+
+pub fn watch(day: usize, solver: Solver, part: Option<u8>, example: Option<PathBuf>) -> ! {
+    let mut files = vec![PathBuf::from(format!("input/input{day}.txt"))];
+    files.extend(example);
+    println!("Watching day {day}: {}", files.iter().map(|f| f.display().to_string()).collect::<Vec<_>>().join(", "));
+
+    let mut watcher = Watcher::new(solver, part, files);
+    loop {
+        for run in watcher.poll() {
+            println!("[day {day}] {run}");
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_lines(input: Vec<String>) -> String {
+        input.len().to_string()
+    }
+
+    fn first_line(input: Vec<String>) -> String {
+        input[0].clone()
+    }
+
+    #[test]
+    fn watcher_reruns_on_change() {
+        let file = std::env::temp_dir().join(format!("aoc-2022-watch-{}.txt", std::process::id()));
+        std::fs::write(&file, "a\nb\n").unwrap();
+        let solver = Solver { part1: count_lines, part2: first_line };
+        let mut watcher = Watcher::new(solver, None, vec![file.clone()]);
+
+        let runs = watcher.poll();
+        assert_eq!(2, runs.len());
+        assert_eq!(Ok(String::from("2")), runs[0].answer);
+        assert_eq!(None, runs[0].previous);
+        assert!(watcher.poll().is_empty());
+
+        std::fs::write(&file, "c\nd\ne\n").unwrap();
+        let runs = watcher.poll();
+        assert_eq!(format!("{} part1: 2 -> 3", file.display()), runs[0].to_string());
+        assert_eq!(format!("{} part2: a -> c", file.display()), runs[1].to_string());
+
+        std::fs::write(&file, "").unwrap();
+        let runs = watcher.poll();
+        assert_eq!(Ok(String::from("0")), runs[0].answer);
+        assert!(runs[1].answer.is_err());
+        std::fs::remove_file(file).unwrap();
+    }
+}